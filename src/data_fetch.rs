@@ -1,19 +1,38 @@
-use serde_json;
 use scraper::{Selector};
 use nalgebra::{DMatrix, DVector};
+use futures::future::join_all;
+use crate::models::payoff::OptionType;
 use crate::visualisations::visualisations::plot_iv_curve_reciprocal;
 
-pub async fn fetch_risk_free_rate(api_key: &str) -> Result<f64, Box<dyn std::error::Error>> {
+// Constant-maturity Treasury series and their maturities in years.
+pub const TREASURY_SERIES: [(&str, f64); 11] = [
+    ("DGS1MO", 1.0 / 12.0),
+    ("DGS3MO", 0.25),
+    ("DGS6MO", 0.5),
+    ("DGS1", 1.0),
+    ("DGS2", 2.0),
+    ("DGS3", 3.0),
+    ("DGS5", 5.0),
+    ("DGS7", 7.0),
+    ("DGS10", 10.0),
+    ("DGS20", 20.0),
+    ("DGS30", 30.0),
+];
+
+// Latest numeric observation of a FRED series, as a decimal. Daily series
+// report "." on holidays, so a few observations are fetched and the first
+// usable one is kept.
+async fn fetch_latest_fred_value(api_key: &str, series_id: &str) -> Result<f64, Box<dyn std::error::Error>> {
     let url = format!(
-        "https://api.stlouisfed.org/fred/series/observations?series_id=GS1&api_key={}&file_type=json&sort_order=desc&limit=1",
-        api_key
+        "https://api.stlouisfed.org/fred/series/observations?series_id={}&api_key={}&file_type=json&sort_order=desc&limit=10",
+        series_id, api_key
     );
 
     let resp = reqwest::get(&url).await?.json::<serde_json::Value>().await?;
 
     if let Some(observations) = resp["observations"].as_array() {
-        if let Some(latest) = observations.first() {
-            if let Some(value_str) = latest["value"].as_str() {
+        for observation in observations {
+            if let Some(value_str) = observation["value"].as_str() {
                 if let Ok(value) = value_str.parse::<f64>() {
                     return Ok(value / 100.0);
                 }
@@ -21,16 +40,32 @@ pub async fn fetch_risk_free_rate(api_key: &str) -> Result<f64, Box<dyn std::err
         }
     }
 
-    Err("Failed to parse risk-free rate".into())
+    Err(format!("Failed to parse {} from FRED", series_id).into())
 }
 
-pub async fn predict_iv(
-    symbol: &str,
-    input_strike: f64,
-    predict_expiry: u64,
-    option_type: &str,
-    plot_graph: bool
-) -> Result<f64, Box<dyn std::error::Error>> {
+pub async fn fetch_risk_free_rate(api_key: &str) -> Result<f64, Box<dyn std::error::Error>> {
+    fetch_latest_fred_value(api_key, "GS1").await
+}
+
+// (maturity in years, par yield) for every Treasury tenor that returned a
+// value, ordered by maturity.
+pub async fn fetch_treasury_par_yields(api_key: &str) -> Result<Vec<(f64, f64)>, Box<dyn std::error::Error>> {
+    let requests = TREASURY_SERIES.iter().map(|(series_id, _)| fetch_latest_fred_value(api_key, series_id));
+    let par_yields: Vec<(f64, f64)> = join_all(requests)
+        .await
+        .into_iter()
+        .zip(TREASURY_SERIES.iter())
+        .filter_map(|(value, (_, maturity))| value.ok().map(|y| (*maturity, y)))
+        .collect();
+
+    if par_yields.is_empty() {
+        return Err("Failed to fetch any Treasury yields".into());
+    }
+
+    Ok(par_yields)
+}
+
+pub async fn fetch_expiration_dates(symbol: &str) -> Result<Vec<u64>, Box<dyn std::error::Error>> {
     let url = format!("https://finance.yahoo.com/quote/{}/options", symbol);
 
     let client = reqwest::Client::new();
@@ -68,6 +103,18 @@ pub async fn predict_iv(
         return Err("Could not find expiration dates".into());
     }
 
+    Ok(expiries)
+}
+
+pub async fn predict_iv(
+    symbol: &str,
+    input_strike: f64,
+    predict_expiry: u64,
+    option_type: OptionType,
+    plot_graph: bool
+) -> Result<f64, Box<dyn std::error::Error>> {
+    let expiries = fetch_expiration_dates(symbol).await?;
+
     let mut expiry_iv_pairs = Vec::new();
 
     for expiry in &expiries {
@@ -150,12 +197,30 @@ pub async fn predict_iv(
     Ok(predicted_iv)
 }
 
-pub async fn fetch_closest_iv_for_expiry(
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OptionQuote {
+    pub strike: f64,
+    pub last_price: f64,
+    pub bid: f64,
+    pub ask: f64,
+    pub implied_volatility: f64,
+}
+
+impl OptionQuote {
+    pub fn mid(&self) -> f64 {
+        0.5 * (self.bid + self.ask)
+    }
+}
+
+fn parse_cell(text: String) -> Option<f64> {
+    text.replace(['%', ','], "").trim().parse::<f64>().ok()
+}
+
+pub async fn fetch_option_chain(
     symbol: &str,
     expiry: u64,
-    input_strike: f64,
-    option_type: &str,
-) -> Result<f64, Box<dyn std::error::Error>> {
+    option_type: OptionType,
+) -> Result<Vec<OptionQuote>, Box<dyn std::error::Error>> {
     let url = format!("https://finance.yahoo.com/quote/{}/options?date={}", symbol, expiry);
 
     let client = reqwest::Client::new();
@@ -172,8 +237,8 @@ pub async fn fetch_closest_iv_for_expiry(
     let tables: Vec<_> = document.select(&table_selector).collect();
 
     let table_index = match option_type {
-        "put" => 1,
-        _ => 0,
+        OptionType::Call => 0,
+        OptionType::Put => 1,
     };
 
     let table = tables.get(table_index)
@@ -183,32 +248,48 @@ pub async fn fetch_closest_iv_for_expiry(
     let cell_selector = Selector::parse("td.yf-wurt5d").unwrap();
     let bold_selector = Selector::parse("td.bold.yf-wurt5d").unwrap();
 
-    let mut best_iv: Option<f64> = None;
-    let mut min_diff = f64::MAX;
+    let mut quotes = Vec::new();
 
     for row in table.select(&row_selector) {
         let cells: Vec<_> = row.select(&cell_selector).collect();
         let bold_cells: Vec<_> = row.select(&bold_selector).collect();
 
-        if bold_cells.len() >= 2 {
-            let strike_text = bold_cells[1].text().collect::<String>().replace(',', "");
-            if let Ok(strike) = strike_text.trim().parse::<f64>() {
-                if let Some(iv_cell) = cells.last() {
-                    let iv_text = iv_cell.text().collect::<String>().replace('%', "").replace(',', "");
-                    if let Ok(iv) = iv_text.trim().parse::<f64>() {
-                        if iv > 0.0 {
-                            let diff = (strike - input_strike).abs();
-                            if diff < min_diff {
-                                min_diff = diff;
-                                best_iv = Some(iv / 100.0);
-                            }
-                        }
-                    }
-                }
+        if bold_cells.len() >= 2 && cells.len() >= 6 {
+            let cell_value = |i: usize| parse_cell(cells[i].text().collect::<String>());
+            if let Some(strike) = parse_cell(bold_cells[1].text().collect::<String>()) {
+                quotes.push(OptionQuote {
+                    strike,
+                    last_price: cell_value(3).unwrap_or(0.0),
+                    bid: cell_value(4).unwrap_or(0.0),
+                    ask: cell_value(5).unwrap_or(0.0),
+                    implied_volatility: cell_value(cells.len() - 1).unwrap_or(0.0) / 100.0,
+                });
             }
         }
     }
 
+    Ok(quotes)
+}
+
+pub async fn fetch_closest_iv_for_expiry(
+    symbol: &str,
+    expiry: u64,
+    input_strike: f64,
+    option_type: OptionType,
+) -> Result<f64, Box<dyn std::error::Error>> {
+    let quotes = fetch_option_chain(symbol, expiry, option_type).await?;
+
+    let mut best_iv: Option<f64> = None;
+    let mut min_diff = f64::MAX;
+
+    for quote in quotes.iter().filter(|q| q.implied_volatility > 0.0) {
+        let diff = (quote.strike - input_strike).abs();
+        if diff < min_diff {
+            min_diff = diff;
+            best_iv = Some(quote.implied_volatility);
+        }
+    }
+
     best_iv.ok_or_else(|| format!("No in-the-money {}s with nonzero IV found in HTML", option_type).into())
 }
 
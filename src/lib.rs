@@ -0,0 +1,5 @@
+#![allow(clippy::too_many_arguments)]
+
+pub mod models;
+pub mod data_fetch;
+pub mod visualisations;
@@ -1,35 +1,59 @@
-mod models;
-mod data_fetch;
-mod visualisations;
-use data_fetch::{fetch_stock_price, fetch_risk_free_rate, predict_iv};
-use crate::visualisations::visualisations::{plot_greeks, plot_volatility_smile, plot_time_decay, plot_pnl_distribution};
-use models::black_scholes::{black_scholes_call, black_scholes_put};
-use models::monte_carlo::monte_carlo_option_price;
-use std::time::{SystemTime, UNIX_EPOCH};
+use vegaflow::data_fetch::{fetch_stock_price, fetch_treasury_par_yields, fetch_option_chain, fetch_expiration_dates, predict_iv};
+use vegaflow::visualisations::visualisations::{plot_greeks, plot_volatility_smile, plot_time_decay, plot_pnl_distribution, plot_stock_paths};
+use vegaflow::models::black_scholes::black_scholes_price;
+use vegaflow::models::greeks::{black_scholes_greeks, black_scholes_greeks_with_dividends};
+use vegaflow::models::black76::black76_price;
+use vegaflow::models::bachelier::bachelier_implied_vol;
+use vegaflow::models::monte_carlo::{european_monte_carlo_price_with_dividends, monte_carlo_greeks, monte_carlo_option_price, simulate_gbm_paths, GreekMethod};
+use vegaflow::models::binomial::{binomial_option_price, binomial_option_price_with_dividends, TreeMethod};
+use vegaflow::models::lsm::longstaff_schwartz_price;
+use vegaflow::models::payoff::{ExerciseStyle, OptionType, Payoff, Vanilla};
+use vegaflow::models::pde::crank_nicolson_price;
+use vegaflow::models::barrier::{barrier_option_price, barrier_monte_carlo_price, BarrierType, MonitoringCorrection};
+use vegaflow::models::asian::{asian_monte_carlo_price, turnbull_wakeman_price, AsianType};
+use vegaflow::models::lookback::{floating_strike_lookback_price, lookback_monte_carlo_price, LookbackType};
+use vegaflow::models::digital::{digital_option_price, digital_monte_carlo_price, DigitalType};
+use vegaflow::models::calendar::{Date, TimeBasis};
+use vegaflow::models::dividends::CashDividend;
+use vegaflow::models::curve::{bootstrap_zero_curve, CurveInterpolation, DiscountCurve};
+use vegaflow::models::rates::{forward_swap_rate, swaption_price, RateModel, SwaptionType};
+use vegaflow::models::implied_vol::implied_volatility_from_quotes;
+use vegaflow::models::heston::{heston_call, heston_put, HestonParams};
+use vegaflow::models::calibration::CalibrationQuote;
+use vegaflow::models::heston_calibration::{calibrate_heston, HestonBounds};
+use vegaflow::models::sabr::{calibrate_sabr, sabr_smile, SabrFormula};
+use vegaflow::models::svi::{SmileSlice, SsviSurface, SviSurface};
+use vegaflow::models::vol_surface::{check_arbitrage, VolatilitySurface};
+use vegaflow::models::local_vol::{local_vol_monte_carlo_price, LocalVolGrid};
+use vegaflow::models::merton_jump::{merton_jump_call, merton_jump_put, MertonJumpModel, MertonJumpParams};
+use vegaflow::models::fourier::{fourier_smile, FourierMethod};
+use vegaflow::models::variance_gamma::{calibrate_variance_gamma, VarianceGammaParams};
+use vegaflow::models::nig::{calibrate_nig, NigParams};
 use futures::future::join_all;
 
-fn time_to_maturity_in_years(expiry_unix: u64) -> f64 {
-    let now_unix = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
-    let seconds_to_expiry = if expiry_unix > now_unix {
-        expiry_unix - now_unix
-    } else {
-        0
-    };
-    seconds_to_expiry as f64 / (365.25 * 24.0 * 60.0 * 60.0)
+fn time_to_maturity_in_years(expiry_unix: u64, basis: TimeBasis) -> f64 {
+    basis.year_fraction(Date::today(), Date::from_unix(expiry_unix)).max(0.0)
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let fred_api_key = ""; // fred API key
-    let option_type = "call"; // option type
+    let option_type = OptionType::Call; // option type
+    let basis = TimeBasis::Calendar; // calendar or trading time
     let symbol = "AAPL"; // stock symbol
     let expiry = 1750118762; // expiry date
     let current_stock = fetch_stock_price(symbol).await?; // fetch stock price
     let k = 95.0;       // strike price
-    let t = time_to_maturity_in_years(expiry); // time to maturity in years
-    let r = fetch_risk_free_rate(fred_api_key) // risk-free rate
+    let t = time_to_maturity_in_years(expiry, basis); // time to maturity in years
+    let par_yields = fetch_treasury_par_yields(fred_api_key) // Treasury curve
             .await
-            .expect("Failed to fetch risk-free rate");
+            .expect("Failed to fetch Treasury yields");
+    let curve = bootstrap_zero_curve(&par_yields, CurveInterpolation::MonotoneConvex)?;
+    let r = curve.zero_rate(t); // risk-free rate to this expiry
+    let q = 0.0; // continuous dividend yield
+    let cash_dividends: Vec<CashDividend> = vec![]; // known cash dividends (years to ex-date, amount), e.g. CashDividend::new(0.1, 0.26)
+    let dividends: Vec<CashDividend> = cash_dividends.into_iter().filter(|d| d.time <= t).collect();
+    let payoff = Vanilla::new(option_type, k);
     let calculated_iv = predict_iv(symbol, k, expiry, option_type, true).await?;
     let num_points = 15;
     let strike_min = (current_stock * 0.8).round();
@@ -50,22 +74,194 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .into_iter()
         .collect::<Result<_, _>>()?;
         
-    plot_volatility_smile(strikes, ivs, current_stock, k)?;
-    plot_greeks(current_stock, k, t, r, calculated_iv, option_type)?;
-    plot_time_decay(current_stock, k, r, calculated_iv, 180, option_type)?;
-    plot_pnl_distribution(current_stock, k, t, r, calculated_iv, option_type, 100000)?;
-
-    if option_type == "call" {
-        let call_price_black_scholes = black_scholes_call(current_stock, k, t, r, calculated_iv);
-        let call_price_monte_carlo = monte_carlo_option_price(current_stock, k, t, r, calculated_iv, option_type, 100000);
-        println!("Black-Scholes Call Price: {:.9}", call_price_black_scholes);
-        println!("Monte-Carlo Call Price: {:.9}", call_price_monte_carlo);
+    let forward = current_stock * ((r - q) * t).exp();
+    let (first_strike, last_strike) = (strikes[0], strikes[strikes.len() - 1]);
+    let fine_strikes: Vec<f64> = (0..=100)
+        .map(|i| first_strike + (last_strike - first_strike) * i as f64 / 100.0)
+        .collect();
+    let mut fitted_smiles = Vec::new();
+    match calibrate_sabr(forward, t, &strikes, &ivs, 0.5, SabrFormula::Obloj) {
+        Ok(fit) => {
+            println!("SABR fit (beta=0.5): alpha={:.4}, rho={:.4}, nu={:.4}, RMSE {:.4} vol",
+                     fit.params.alpha, fit.params.rho, fit.params.nu, fit.rmse);
+            fitted_smiles.push(("SABR fit", sabr_smile(forward, t, &fit.params, SabrFormula::Obloj, &fine_strikes)));
+        }
+        Err(e) => println!("SABR calibration failed: {}", e),
+    }
+
+    let earnings_gaps = MertonJumpParams::new(4.0, -0.01, 0.05); // quarterly earnings jumps
+    let jump_model = MertonJumpModel { sigma: calculated_iv, jumps: earnings_gaps };
+    fitted_smiles.push(("Merton jump (COS)", fourier_smile(&jump_model, current_stock, &fine_strikes, t, r, q, FourierMethod::Cos)));
+
+    plot_volatility_smile(strikes, ivs, current_stock, k, &fitted_smiles)?;
+    plot_greeks(current_stock, t, r, q, calculated_iv, &payoff, &dividends, basis)?;
+    plot_time_decay(current_stock, r, q, calculated_iv, 180, &payoff, &dividends, basis)?;
+    plot_pnl_distribution(current_stock, t, r, q, calculated_iv, &payoff, &dividends, 100000, basis)?;
+
+    let price_black_scholes = black_scholes_price(current_stock, k, t, r, q, calculated_iv, option_type);
+    let price_monte_carlo = monte_carlo_option_price(current_stock, t, r, q, calculated_iv, &payoff, 100000);
+    println!("Black-Scholes {} Price: {:.9}", payoff.label(), price_black_scholes);
+    println!("Monte-Carlo {} Price: {:.9}", payoff.label(), price_monte_carlo);
+
+    let greeks = black_scholes_greeks(current_stock, k, t, r, q, calculated_iv, option_type, basis);
+    println!("Black-Scholes Delta: {:.6}, Gamma: {:.6}, Theta: {:.6}/day, Vega: {:.6}/vol pt, Rho: {:.6}/%, Dividend Rho: {:.6}/%",
+             greeks.delta, greeks.gamma, greeks.theta, greeks.vega, greeks.rho, greeks.dividend_rho);
+    println!("Black-Scholes Vanna: {:.6}, Volga: {:.6}, Charm: {:.6}/day, Speed: {:.6}, Color: {:.6}/day, Zomma: {:.6}, Ultima: {:.6}",
+             greeks.vanna, greeks.volga, greeks.charm, greeks.speed, greeks.color, greeks.zomma, greeks.ultima);
+    println!("Black-Scholes Dual Delta: {:.6}, Dual Gamma: {:.6}", greeks.dual_delta, greeks.dual_gamma);
+    for method in [GreekMethod::Pathwise, GreekMethod::LikelihoodRatio] {
+        let mc = monte_carlo_greeks(current_stock, t, r, q, calculated_iv, &payoff, method, 100000);
+        println!("Monte-Carlo {:?} Delta: {:.6} +/- {:.6}, Gamma: {:.6} +/- {:.6}, Vega: {:.6} +/- {:.6}, Rho: {:.6} +/- {:.6}",
+                 mc.method, mc.delta.value, mc.delta.std_error, mc.gamma.value, mc.gamma.std_error,
+                 mc.vega.value, mc.vega.std_error, mc.rho.value, mc.rho.std_error);
+    }
+
+    if !dividends.is_empty() {
+        let escrowed = black_scholes_greeks_with_dividends(current_stock, k, t, r, q, calculated_iv, option_type, &dividends, basis);
+        let escrowed_monte_carlo = european_monte_carlo_price_with_dividends(current_stock, t, r, q, calculated_iv, &payoff, &dividends, 100000);
+        let escrowed_american = binomial_option_price_with_dividends(current_stock, t, r, q, calculated_iv, &payoff, &ExerciseStyle::American, 501,
+                                                                      TreeMethod::LeisenReimer, &dividends, basis);
+        println!("{} Cash Dividends: Black-Scholes {:.9} (Delta {:.6}, Theta {:.6}/day), Monte-Carlo {:.9}, Binomial American {:.9}",
+                 dividends.len(), escrowed.price, escrowed.delta, escrowed.theta, escrowed_monte_carlo, escrowed_american.price);
+    }
+
+    let black76 = black76_price(forward, k, t, r, calculated_iv, option_type);
+    match bachelier_implied_vol(black76, forward, k, t, r, option_type) {
+        Ok(normal_vol) => println!("Black-76 Price on the forward: {:.9} (normal vol {:.4})", black76, normal_vol),
+        Err(e) => println!("Black-76 Price on the forward: {:.9} (no normal vol: {})", black76, e),
+    }
+
+    let jump_price = match option_type {
+        OptionType::Call => merton_jump_call(current_stock, k, t, r, q, calculated_iv, &earnings_gaps),
+        OptionType::Put => merton_jump_put(current_stock, k, t, r, q, calculated_iv, &earnings_gaps),
+    };
+    println!("Merton Jump-Diffusion Price: {:.9}", jump_price);
+
+    let american = binomial_option_price(current_stock, t, r, q, calculated_iv, &payoff, &ExerciseStyle::American, 501, TreeMethod::LeisenReimer, basis);
+    println!("Binomial (Leisen-Reimer) American Price: {:.9}", american.price);
+    println!("Binomial Delta: {:.6}, Gamma: {:.6}, Theta: {:.6}", american.delta, american.gamma, american.theta);
+
+    let pde_european = crank_nicolson_price(current_stock, t, r, q, calculated_iv, &payoff, &ExerciseStyle::European, None, 400, 200, basis);
+    let pde_american = crank_nicolson_price(current_stock, t, r, q, calculated_iv, &payoff, &ExerciseStyle::American, None, 400, 200, basis);
+    println!("Crank-Nicolson European Price: {:.9}, American Price: {:.9}", pde_european.price, pde_american.price);
+    println!("Crank-Nicolson Delta: {:.6}, Gamma: {:.6}, Theta: {:.6}", pde_european.delta, pde_european.gamma, pde_european.theta);
+
+    let barrier_level = current_stock * 0.85; // down-and-out barrier
+    let barrier_analytic = barrier_option_price(current_stock, k, barrier_level, 0.0, t, r, q, calculated_iv, option_type, BarrierType::DownAndOut);
+    let barrier_monte_carlo = barrier_monte_carlo_price(current_stock, barrier_level, 0.0, t, r, q, calculated_iv, &payoff,
+                                                        BarrierType::DownAndOut, MonitoringCorrection::BrownianBridge, 100, 50000);
+    println!("Down-and-Out (H={:.2}) Price: analytic {:.9}, Monte-Carlo {:.9}", barrier_level, barrier_analytic, barrier_monte_carlo);
+    let barrier_paths = simulate_gbm_paths(current_stock, t, r, q, calculated_iv, 100, 50);
+    plot_stock_paths(current_stock, k, t, barrier_paths, Some(barrier_level))?;
+
+    let asian_turnbull_wakeman = turnbull_wakeman_price(current_stock, k, t, r, q, calculated_iv, option_type);
+    let asian_monte_carlo = asian_monte_carlo_price(current_stock, k, t, r, q, calculated_iv, option_type, AsianType::AverageRate, 100, 50000);
+    println!("Asian Average-Rate Price: Turnbull-Wakeman {:.9}, Monte-Carlo {:.9} +/- {:.9} (+/- {:.9} without control variate)",
+             asian_turnbull_wakeman, asian_monte_carlo.price, asian_monte_carlo.std_error, asian_monte_carlo.uncontrolled_std_error);
+
+    let lookback_analytic = floating_strike_lookback_price(current_stock, current_stock, t, r, q, calculated_iv, option_type);
+    let lookback_monte_carlo = lookback_monte_carlo_price(current_stock, k, current_stock, t, r, q, calculated_iv, option_type,
+                                                          LookbackType::FloatingStrike, MonitoringCorrection::BrownianBridge, 100, 50000);
+    println!("Floating-Strike Lookback Price: analytic {:.9}, Monte-Carlo {:.9}", lookback_analytic, lookback_monte_carlo);
+
+    let digital_analytic = digital_option_price(current_stock, k, t, r, q, calculated_iv, option_type, DigitalType::CashOrNothing(1.0));
+    let digital_monte_carlo = digital_monte_carlo_price(current_stock, k, t, r, q, calculated_iv, option_type, DigitalType::CashOrNothing(1.0), 100000);
+    println!("Cash-or-Nothing Digital Price: analytic {:.9}, Monte-Carlo {:.9}", digital_analytic, digital_monte_carlo);
+
+    let swap_rate = forward_swap_rate(&curve, 1.0, 6.0, 2);
+    let payer = swaption_price(&curve, 1_000_000.0, 1.0, 5.0, 2, swap_rate, 0.2, SwaptionType::Payer, RateModel::Black);
+    println!("1Yx5Y forward swap rate {:.4}%, ATM payer swaption (20% Black vol) on 1mm: {:.2}", swap_rate * 100.0, payer);
+
+    let lsm = longstaff_schwartz_price(current_stock, t, r, q, calculated_iv, &payoff, &ExerciseStyle::American, 50, 50000);
+    println!("Longstaff-Schwartz American Price: {:.9} (lower bound {:.9} +/- {:.9})", lsm.price, lsm.lower_bound, lsm.std_error);
+
+    let chain = fetch_option_chain(symbol, expiry, option_type).await?;
+    if let Some(quote) = chain.iter().min_by(|a, b| (a.strike - k).abs().partial_cmp(&(b.strike - k).abs()).unwrap()) {
+        match implied_volatility_from_quotes(quote.bid, quote.ask, current_stock, quote.strike, t, r, q, option_type) {
+            Ok(vols) => println!("Solved IV at K={:.2}: bid {:?}, mid {:.6}, ask {:?} (Yahoo {:.6})",
+                                 quote.strike, vols.bid, vols.mid, vols.ask, quote.implied_volatility),
+            Err(e) => println!("Could not solve IV at K={:.2}: {}", quote.strike, e),
+        }
+    }
+
+    let mut calibration_quotes = Vec::new();
+    let mut smile_slices = Vec::new();
+    for chain_expiry in fetch_expiration_dates(symbol).await?.into_iter().take(4) {
+        let chain_t = time_to_maturity_in_years(chain_expiry, basis);
+        if chain_t <= 0.0 {
+            continue;
+        }
+        let chain_r = curve.zero_rate(chain_t);
+        let mut slice = SmileSlice { t: chain_t, r: chain_r, forward: current_stock * ((chain_r - q) * chain_t).exp(), strikes: Vec::new(), ivs: Vec::new() };
+        // out-of-the-money quotes only: puts below the forward, calls above
+        let puts = fetch_option_chain(symbol, chain_expiry, OptionType::Put).await?
+            .into_iter()
+            .filter(|quote| quote.strike < slice.forward);
+        let calls = fetch_option_chain(symbol, chain_expiry, OptionType::Call).await?
+            .into_iter()
+            .filter(|quote| quote.strike >= slice.forward);
+        for quote in puts.chain(calls) {
+            if quote.implied_volatility > 0.0 && (quote.strike / current_stock - 1.0).abs() <= 0.2 {
+                calibration_quotes.push(CalibrationQuote { strike: quote.strike, t: chain_t, r: chain_r, implied_vol: quote.implied_volatility });
+                slice.strikes.push(quote.strike);
+                slice.ivs.push(quote.implied_volatility);
+            }
+        }
+        if !slice.strikes.is_empty() {
+            smile_slices.push(slice);
+        }
+    }
+
+    let slice_times: Vec<f64> = smile_slices.iter().map(|slice| slice.t).collect();
+    let log_moneyness_grid: Vec<f64> = (-20..=20).map(|i| i as f64 * 0.02).collect();
+    match SviSurface::fit(current_stock, q, &smile_slices) {
+        Ok(surface) => {
+            let report = check_arbitrage(&surface, &slice_times, &log_moneyness_grid);
+            println!("SVI surface IV at K={:.2}, T={:.4}: {:.6} ({} butterfly / {} calendar violations)",
+                     k, t, surface.implied_vol(k, t), report.butterfly_violations.len(), report.calendar_violations.len());
+        }
+        Err(e) => println!("SVI surface fit failed: {}", e),
+    }
+    match SsviSurface::fit(current_stock, q, &smile_slices) {
+        Ok(surface) => {
+            println!("SSVI surface IV at K={:.2}, T={:.4}: {:.6} (RMSE {:.4} vol, no-arbitrage conditions {})",
+                     k, t, surface.implied_vol(k, t), surface.rmse_iv,
+                     if surface.satisfies_no_arbitrage_conditions() { "hold" } else { "violated" });
+            let grid = LocalVolGrid::from_surface(&surface, t, 50, current_stock * 0.4, current_stock * 2.0, 81);
+            let local_vol_price = local_vol_monte_carlo_price(current_stock, t, r, q, &grid, &payoff, 100, 50000);
+            println!("Local-Vol Monte-Carlo Price: {:.9}", local_vol_price);
+        }
+        Err(e) => println!("SSVI surface fit failed: {}", e),
+    }
+
+    let initial_variance = calculated_iv * calculated_iv;
+    let initial_heston = HestonParams::new(initial_variance, 2.0, initial_variance, 0.5, -0.5);
+    match calibrate_heston(current_stock, q, &calibration_quotes, &initial_heston, &HestonBounds::default(), 100) {
+        Ok(fit) => {
+            let p = fit.params;
+            println!("Heston fit: v0={:.4}, kappa={:.4}, theta={:.4}, xi={:.4}, rho={:.4}", p.v0, p.kappa, p.theta, p.xi, p.rho);
+            println!("Heston fit quality: RMSE {:.4} vol, max error {:.4} vol, {} iterations ({}), Feller ratio {:.3} ({})",
+                     fit.rmse_iv, fit.max_abs_iv_error, fit.iterations,
+                     if fit.converged { "converged" } else { "stalled" }, fit.feller_ratio,
+                     if fit.feller_satisfied { "satisfied" } else { "violated" });
+            let heston_price = match option_type {
+                OptionType::Call => heston_call(current_stock, k, t, r, q, &p),
+                OptionType::Put => heston_put(current_stock, k, t, r, q, &p),
+            };
+            println!("Heston Price: {:.9}", heston_price);
+        }
+        Err(e) => println!("Heston calibration failed: {}", e),
+    }
+
+    match calibrate_variance_gamma(current_stock, q, &calibration_quotes, &VarianceGammaParams::new(calculated_iv, 0.2, -0.1)) {
+        Ok(fit) => println!("Variance Gamma fit: sigma={:.4}, nu={:.4}, theta={:.4}, RMSE {:.4} vol",
+                            fit.params.sigma, fit.params.nu, fit.params.theta, fit.rmse_iv),
+        Err(e) => println!("Variance Gamma calibration failed: {}", e),
     }
-    if option_type == "put" {
-        let put_price_black_scholes = black_scholes_put(current_stock, k, t, r, calculated_iv);
-        let put_price_monte_carlo = monte_carlo_option_price(current_stock, k, t, r, calculated_iv, option_type, 100000);
-        println!("Black-Scholes Put Price: {:.9}", put_price_black_scholes);
-        println!("Monte-Carlo Put Price: {:.9}", put_price_monte_carlo);
+    match calibrate_nig(current_stock, q, &calibration_quotes, &NigParams::new(10.0, -2.0, calculated_iv * calculated_iv * 10.0)) {
+        Ok(fit) => println!("NIG fit: alpha={:.4}, beta={:.4}, delta={:.4}, RMSE {:.4} vol",
+                            fit.params.alpha, fit.params.beta, fit.params.delta, fit.rmse_iv),
+        Err(e) => println!("NIG calibration failed: {}", e),
     }
     Ok(())
 }
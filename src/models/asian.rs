@@ -0,0 +1,218 @@
+use crate::models::black_scholes::norm_cdf;
+use crate::models::monte_carlo::simulate_gbm_paths;
+use crate::models::payoff::OptionType;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AsianType {
+    AverageRate,
+    AverageStrike,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AsianMonteCarloResult {
+    pub price: f64,
+    pub std_error: f64,
+    pub uncontrolled_std_error: f64,
+}
+
+// E[(X1 - X2)^+] for jointly lognormal X1, X2 given the means and variances of
+// their logs and the covariance between them.
+fn lognormal_exchange(m1: f64, v1: f64, m2: f64, v2: f64, covariance: f64) -> f64 {
+    let forward1 = (m1 + 0.5 * v1).exp();
+    let forward2 = (m2 + 0.5 * v2).exp();
+    let v = (v1 + v2 - 2.0 * covariance).max(1e-16);
+    let d1 = ((forward1 / forward2).ln() + 0.5 * v) / v.sqrt();
+    forward1 * norm_cdf(d1) - forward2 * norm_cdf(d1 - v.sqrt())
+}
+
+fn generalized_black_scholes(s: f64, k: f64, t: f64, r: f64, b: f64, sigma: f64, option_type: OptionType) -> f64 {
+    let d1 = ((s / k).ln() + (b + 0.5 * sigma * sigma) * t) / (sigma * t.sqrt());
+    let d2 = d1 - sigma * t.sqrt();
+    let carry = ((b - r) * t).exp();
+    match option_type {
+        OptionType::Call => s * carry * norm_cdf(d1) - k * (-r * t).exp() * norm_cdf(d2),
+        OptionType::Put => k * (-r * t).exp() * norm_cdf(-d2) - s * carry * norm_cdf(-d1),
+    }
+}
+
+// Kemna-Vorst: a continuously sampled geometric average-rate option is a
+// Black-Scholes option with vol sigma / sqrt(3) and carry (b - sigma^2 / 6) / 2.
+pub fn kemna_vorst_price(s: f64, k: f64, t: f64, r: f64, q: f64, sigma: f64, option_type: OptionType) -> f64 {
+    let adjusted_carry = 0.5 * (r - q - sigma * sigma / 6.0);
+    generalized_black_scholes(s, k, t, r, adjusted_carry, sigma / 3.0_f64.sqrt(), option_type)
+}
+
+// Exact price of the geometric-average option sampled at t_i = i T / n,
+// i = 1..n, for both average-rate and average-strike payoffs.
+pub fn discrete_geometric_asian_price(
+    s: f64,
+    k: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    option_type: OptionType,
+    asian_type: AsianType,
+    num_fixings: usize,
+) -> f64 {
+    let n = num_fixings.max(1) as f64;
+    let mu = r - q - 0.5 * sigma * sigma;
+    let mean_time = t * (n + 1.0) / (2.0 * n);
+
+    let m_avg = s.ln() + mu * mean_time;
+    let v_avg = sigma * sigma * t * (n + 1.0) * (2.0 * n + 1.0) / (6.0 * n * n);
+    let m_spot = s.ln() + mu * t;
+    let v_spot = sigma * sigma * t;
+    let covariance = sigma * sigma * mean_time;
+
+    let undiscounted = match (asian_type, option_type) {
+        (AsianType::AverageRate, OptionType::Call) => lognormal_exchange(m_avg, v_avg, k.ln(), 0.0, 0.0),
+        (AsianType::AverageRate, OptionType::Put) => lognormal_exchange(k.ln(), 0.0, m_avg, v_avg, 0.0),
+        (AsianType::AverageStrike, OptionType::Call) => lognormal_exchange(m_spot, v_spot, m_avg, v_avg, covariance),
+        (AsianType::AverageStrike, OptionType::Put) => lognormal_exchange(m_avg, v_avg, m_spot, v_spot, covariance),
+    };
+
+    (-r * t).exp() * undiscounted
+}
+
+// Turnbull-Wakeman: matches the first two moments of the continuous
+// arithmetic average to a lognormal and prices with generalized Black-Scholes.
+pub fn turnbull_wakeman_price(s: f64, k: f64, t: f64, r: f64, q: f64, sigma: f64, option_type: OptionType) -> f64 {
+    let mut b = r - q;
+    if b.abs() < 1e-8 {
+        b = 1e-8;
+    }
+    let sigma2 = sigma * sigma;
+    let m1 = ((b * t).exp() - 1.0) / (b * t);
+    let m2 = 2.0 * ((2.0 * b + sigma2) * t).exp() / ((b + sigma2) * (2.0 * b + sigma2) * t * t)
+        + 2.0 / (b * t * t) * (1.0 / (2.0 * b + sigma2) - (b * t).exp() / (b + sigma2));
+
+    let adjusted_carry = m1.ln() / t;
+    let adjusted_sigma = (m2.ln() / t - 2.0 * adjusted_carry).max(1e-12).sqrt();
+    generalized_black_scholes(s, k, t, r, adjusted_carry, adjusted_sigma, option_type)
+}
+
+// Arithmetic-average Monte Carlo with the discretely sampled geometric option
+// as a control variate; the regression coefficient is estimated in-sample.
+pub fn asian_monte_carlo_price(
+    s: f64,
+    k: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    option_type: OptionType,
+    asian_type: AsianType,
+    num_fixings: usize,
+    num_paths: usize,
+) -> AsianMonteCarloResult {
+    let num_fixings = num_fixings.max(1);
+    let discount = (-r * t).exp();
+    let payoff = |average: f64, terminal: f64| match asian_type {
+        AsianType::AverageRate => option_type.intrinsic(average, k),
+        AsianType::AverageStrike => option_type.intrinsic(terminal, average),
+    };
+
+    let paths = simulate_gbm_paths(s, t, r, q, sigma, num_fixings, num_paths);
+    let (arithmetic, geometric): (Vec<f64>, Vec<f64>) = paths
+        .iter()
+        .map(|path| {
+            let fixings = &path[1..];
+            let terminal = fixings[num_fixings - 1];
+            let arithmetic_average = fixings.iter().sum::<f64>() / num_fixings as f64;
+            let geometric_average = (fixings.iter().map(|x| x.ln()).sum::<f64>() / num_fixings as f64).exp();
+            (discount * payoff(arithmetic_average, terminal), discount * payoff(geometric_average, terminal))
+        })
+        .unzip();
+
+    let n = num_paths as f64;
+    let mean_y = arithmetic.iter().sum::<f64>() / n;
+    let mean_x = geometric.iter().sum::<f64>() / n;
+    let covariance = arithmetic.iter().zip(geometric.iter()).map(|(y, x)| (y - mean_y) * (x - mean_x)).sum::<f64>() / (n - 1.0);
+    let variance_x = geometric.iter().map(|x| (x - mean_x).powi(2)).sum::<f64>() / (n - 1.0);
+    let variance_y = arithmetic.iter().map(|y| (y - mean_y).powi(2)).sum::<f64>() / (n - 1.0);
+    let beta = if variance_x > 0.0 { covariance / variance_x } else { 0.0 };
+
+    let exact_geometric = discrete_geometric_asian_price(s, k, t, r, q, sigma, option_type, asian_type, num_fixings);
+    let controlled: Vec<f64> = arithmetic
+        .iter()
+        .zip(geometric.iter())
+        .map(|(y, x)| y - beta * (x - exact_geometric))
+        .collect();
+    let price = controlled.iter().sum::<f64>() / n;
+    let variance = controlled.iter().map(|v| (v - price).powi(2)).sum::<f64>() / (n - 1.0);
+
+    AsianMonteCarloResult {
+        price,
+        std_error: (variance / n).sqrt(),
+        uncontrolled_std_error: (variance_y / n).sqrt(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const S: f64 = 100.0;
+    const K: f64 = 100.0;
+    const T: f64 = 1.0;
+    const R: f64 = 0.04;
+    const Q: f64 = 0.01;
+    const SIGMA: f64 = 0.25;
+
+    #[test]
+    fn geometric_closed_form_matches_simulation() {
+        let num_fixings = 12;
+        let paths = simulate_gbm_paths(S, T, R, Q, SIGMA, num_fixings, 100_000);
+        for asian_type in [AsianType::AverageRate, AsianType::AverageStrike] {
+            for option_type in [OptionType::Call, OptionType::Put] {
+                let discounted: Vec<f64> = paths
+                    .iter()
+                    .map(|path| {
+                        let fixings = &path[1..];
+                        let geometric = (fixings.iter().map(|x| x.ln()).sum::<f64>() / num_fixings as f64).exp();
+                        let payoff = match asian_type {
+                            AsianType::AverageRate => option_type.intrinsic(geometric, K),
+                            AsianType::AverageStrike => option_type.intrinsic(fixings[num_fixings - 1], geometric),
+                        };
+                        (-R * T).exp() * payoff
+                    })
+                    .collect();
+                let n = discounted.len() as f64;
+                let mean = discounted.iter().sum::<f64>() / n;
+                let std_error = (discounted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0) / n).sqrt();
+                let exact = discrete_geometric_asian_price(S, K, T, R, Q, SIGMA, option_type, asian_type, num_fixings);
+                assert!((mean - exact).abs() < 4.0 * std_error, "{asian_type:?} {option_type:?}: {mean} +/- {std_error} vs {exact}");
+            }
+        }
+    }
+
+    #[test]
+    fn dense_geometric_sampling_converges_to_kemna_vorst() {
+        for option_type in [OptionType::Call, OptionType::Put] {
+            let discrete = discrete_geometric_asian_price(S, K, T, R, Q, SIGMA, option_type, AsianType::AverageRate, 100_000);
+            let continuous = kemna_vorst_price(S, K, T, R, Q, SIGMA, option_type);
+            assert!((discrete - continuous).abs() < 1e-4, "{option_type:?}: {discrete} vs {continuous}");
+        }
+    }
+
+    #[test]
+    fn turnbull_wakeman_agrees_with_the_arithmetic_simulation() {
+        // Turnbull-Wakeman averages continuously; daily fixings are close
+        // enough that its moment-matching error dominates
+        for option_type in [OptionType::Call, OptionType::Put] {
+            let mc = asian_monte_carlo_price(S, K, T, R, Q, SIGMA, option_type, AsianType::AverageRate, 250, 20_000);
+            let approximation = turnbull_wakeman_price(S, K, T, R, Q, SIGMA, option_type);
+            assert!((mc.price - approximation).abs() < 4.0 * mc.std_error + 0.02 * approximation,
+                    "{option_type:?}: {} +/- {} vs {approximation}", mc.price, mc.std_error);
+        }
+    }
+
+    #[test]
+    fn control_variate_reduces_the_standard_error() {
+        for asian_type in [AsianType::AverageRate, AsianType::AverageStrike] {
+            let mc = asian_monte_carlo_price(S, K, T, R, Q, SIGMA, OptionType::Call, asian_type, 12, 20_000);
+            assert!(mc.std_error < 0.5 * mc.uncontrolled_std_error, "{asian_type:?}: {} vs {}", mc.std_error, mc.uncontrolled_std_error);
+        }
+    }
+}
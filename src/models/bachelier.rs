@@ -0,0 +1,121 @@
+use crate::models::black76::ForwardGreeks;
+use crate::models::black_scholes::{norm_cdf, norm_pdf};
+use crate::models::implied_vol::ImpliedVolError;
+use crate::models::payoff::OptionType;
+use crate::models::calendar::TimeBasis;
+
+const PRICE_TOLERANCE: f64 = 1e-12;
+const MAX_ITERATIONS: usize = 100;
+
+// Normal model: the forward diffuses arithmetically with absolute vol
+// sigma_n, so forwards and strikes may be zero or negative.
+pub fn bachelier_price(f: f64, k: f64, t: f64, r: f64, sigma_n: f64, option_type: OptionType) -> f64 {
+    let phi = option_type.sign();
+    let std_dev = sigma_n * t.sqrt();
+    let d = (f - k) / std_dev;
+    (-r * t).exp() * (phi * (f - k) * norm_cdf(phi * d) + std_dev * norm_pdf(d))
+}
+
+// Vega is per basis point of normal vol, the market convention for rates.
+pub fn bachelier_greeks(f: f64, k: f64, t: f64, r: f64, sigma_n: f64, option_type: OptionType, basis: TimeBasis) -> ForwardGreeks {
+    let phi = option_type.sign();
+    let std_dev = sigma_n * t.sqrt();
+    let d = (f - k) / std_dev;
+    let discount = (-r * t).exp();
+    let price = bachelier_price(f, k, t, r, sigma_n, option_type);
+    ForwardGreeks {
+        price,
+        delta: phi * discount * norm_cdf(phi * d),
+        gamma: discount * norm_pdf(d) / std_dev,
+        vega: discount * t.sqrt() * norm_pdf(d) / 10_000.0,
+        theta: (-discount * sigma_n * norm_pdf(d) / (2.0 * t.sqrt()) + r * price) / basis.days_per_year(),
+        rho: -t * price / 100.0,
+    }
+}
+
+// Implied normal vol. The price is increasing and unbounded in sigma_n, so a
+// Newton step from the at-the-money guess is kept inside a bracket that is
+// widened until it contains the root.
+pub fn bachelier_implied_vol(price: f64, f: f64, k: f64, t: f64, r: f64, option_type: OptionType) -> Result<f64, ImpliedVolError> {
+    if !(price.is_finite() && f.is_finite() && k.is_finite()) || price <= 0.0 {
+        return Err(ImpliedVolError::InvalidInput);
+    }
+    if t <= 0.0 || !t.is_finite() {
+        return Err(ImpliedVolError::Expired);
+    }
+    let lower_bound = (-r * t).exp() * option_type.intrinsic(f, k);
+    if price <= lower_bound + PRICE_TOLERANCE {
+        return Err(ImpliedVolError::BelowIntrinsic { price, lower_bound });
+    }
+
+    let mut sigma = (price * (r * t).exp() * (2.0 * std::f64::consts::PI / t).sqrt()).max(1e-8);
+    let mut lo = 0.0;
+    let mut hi = sigma;
+    while bachelier_price(f, k, t, r, hi, option_type) < price {
+        lo = hi;
+        hi *= 2.0;
+    }
+
+    for _ in 0..MAX_ITERATIONS {
+        let diff = bachelier_price(f, k, t, r, sigma, option_type) - price;
+        if diff.abs() < PRICE_TOLERANCE {
+            return Ok(sigma);
+        }
+        if diff > 0.0 {
+            hi = sigma;
+        } else {
+            lo = sigma;
+        }
+
+        let vega = (-r * t).exp() * t.sqrt() * norm_pdf((f - k) / (sigma * t.sqrt()));
+        let newton = sigma - diff / vega;
+        sigma = if vega > 1e-14 && newton > lo && newton < hi {
+            newton
+        } else {
+            0.5 * (lo + hi)
+        };
+
+        if hi - lo < 1e-15 * hi.max(1.0) {
+            return Ok(sigma);
+        }
+    }
+
+    Err(ImpliedVolError::NoConvergence { iterations: MAX_ITERATIONS, last_vol: sigma })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn greeks_match_finite_differences() {
+        // negative forward and strike, as for rates
+        let (f, k, t, r, sigma_n) = (-0.002, 0.001, 1.5, 0.03, 0.008);
+        for option_type in [OptionType::Call, OptionType::Put] {
+            let greeks = bachelier_greeks(f, k, t, r, sigma_n, option_type, TimeBasis::Calendar);
+            let price = |f: f64, t: f64, r: f64, sigma_n: f64| bachelier_price(f, k, t, r, sigma_n, option_type);
+            let (hf, h) = (1e-6, 1e-6);
+            let delta = (price(f + hf, t, r, sigma_n) - price(f - hf, t, r, sigma_n)) / (2.0 * hf);
+            let vega = (price(f, t, r, sigma_n + h) - price(f, t, r, sigma_n - h)) / (2.0 * h) / 10_000.0;
+            let rho = (price(f, t, r + h, sigma_n) - price(f, t, r - h, sigma_n)) / (2.0 * h) / 100.0;
+            let theta = -(price(f, t + h, r, sigma_n) - price(f, t - h, r, sigma_n)) / (2.0 * h) / TimeBasis::Calendar.days_per_year();
+            assert!((greeks.delta - delta).abs() < 1e-6);
+            assert!((greeks.vega - vega).abs() < 1e-8);
+            // one basis point of normal vol moves the price by about one vega
+            let one_bp = price(f, t, r, sigma_n + 1e-4) - price(f, t, r, sigma_n);
+            assert!((greeks.vega - one_bp).abs() < 1e-3 * greeks.vega.abs(), "{} vs {one_bp}", greeks.vega);
+            assert!((greeks.rho - rho).abs() < 1e-8);
+            assert!((greeks.theta - theta).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn implied_vol_round_trip() {
+        let (f, k, t, r) = (0.01, 0.0125, 0.75, 0.02);
+        for option_type in [OptionType::Call, OptionType::Put] {
+            let price = bachelier_price(f, k, t, r, 0.0065, option_type);
+            let sigma_n = bachelier_implied_vol(price, f, k, t, r, option_type).unwrap();
+            assert!((sigma_n - 0.0065).abs() < 1e-9);
+        }
+    }
+}
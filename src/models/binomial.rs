@@ -0,0 +1,179 @@
+use crate::models::dividends::{CashDividend, dividends_value_at, escrowed_spot};
+use crate::models::payoff::{ExerciseStyle, Payoff};
+use crate::models::calendar::TimeBasis;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TreeMethod {
+    CoxRossRubinstein,
+    LeisenReimer,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TreeResult {
+    pub price: f64,
+    pub delta: f64,
+    pub gamma: f64,
+    pub theta: f64,
+}
+
+pub fn binomial_option_price<P: Payoff>(
+    s: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    payoff: &P,
+    exercise: &ExerciseStyle,
+    steps: usize,
+    method: TreeMethod,
+    basis: TimeBasis,
+) -> TreeResult {
+    binomial_tree(s, t, r, q, sigma, payoff, exercise, steps, method, &[], basis)
+}
+
+pub fn binomial_option_price_with_dividends<P: Payoff>(
+    s: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    payoff: &P,
+    exercise: &ExerciseStyle,
+    steps: usize,
+    method: TreeMethod,
+    dividends: &[CashDividend],
+    basis: TimeBasis,
+) -> TreeResult {
+    binomial_tree(s, t, r, q, sigma, payoff, exercise, steps, method, dividends, basis)
+}
+
+// Peizer-Pratt method 2 inversion used by Leisen-Reimer.
+fn peizer_pratt(z: f64, n: usize) -> f64 {
+    let n = n as f64;
+    let x = z / (n + 1.0 / 3.0 + 0.1 / (n + 1.0));
+    0.5 + z.signum() * 0.5 * (1.0 - (-x * x * (n + 1.0 / 6.0)).exp()).sqrt()
+}
+
+fn tree_parameters(s: f64, k: f64, t: f64, r: f64, q: f64, sigma: f64, steps: usize, method: TreeMethod) -> (f64, f64, f64) {
+    let dt = t / steps as f64;
+    let growth = ((r - q) * dt).exp();
+    match method {
+        TreeMethod::CoxRossRubinstein => {
+            let u = (sigma * dt.sqrt()).exp();
+            let d = 1.0 / u;
+            (u, d, (growth - d) / (u - d))
+        }
+        TreeMethod::LeisenReimer => {
+            let d1 = ((s / k).ln() + (r - q + 0.5 * sigma * sigma) * t) / (sigma * t.sqrt());
+            let d2 = d1 - sigma * t.sqrt();
+            let p = peizer_pratt(d2, steps);
+            let p_bar = peizer_pratt(d1, steps);
+            let u = growth * p_bar / p;
+            let d = (growth - p * u) / (1.0 - p);
+            (u, d, p)
+        }
+    }
+}
+
+fn binomial_tree<P: Payoff>(
+    s: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    payoff: &P,
+    exercise: &ExerciseStyle,
+    steps: usize,
+    method: TreeMethod,
+    dividends: &[CashDividend],
+    basis: TimeBasis,
+) -> TreeResult {
+    // delta and gamma read the nodes at step two, which must be interior
+    let mut n = steps.max(3);
+    if method == TreeMethod::LeisenReimer && n.is_multiple_of(2) {
+        n += 1;
+    }
+
+    let dt = t / n as f64;
+    let discount = (-r * dt).exp();
+    let s_star = escrowed_spot(s, t, r, dividends);
+    let (u, d, p) = tree_parameters(s_star, payoff.strike(), t, r, q, sigma, n, method);
+    let exercisable = exercise.exercise_steps(t, n);
+    let spot_at = |i: usize, j: usize| {
+        s_star * u.powi(j as i32) * d.powi((i - j) as i32) + dividends_value_at(i as f64 * dt, t, r, dividends)
+    };
+
+    let mut values: Vec<f64> = (0..=n).map(|j| payoff.payoff(spot_at(n, j))).collect();
+    let mut step_two = Vec::new();
+    let mut step_one = Vec::new();
+
+    for i in (0..n).rev() {
+        for j in 0..=i {
+            let continuation = discount * (p * values[j + 1] + (1.0 - p) * values[j]);
+            values[j] = if exercisable[i] {
+                continuation.max(payoff.payoff(spot_at(i, j)))
+            } else {
+                continuation
+            };
+        }
+        values.truncate(i + 1);
+        match i {
+            2 => step_two = values.clone(),
+            1 => step_one = values.clone(),
+            _ => {}
+        }
+    }
+
+    let price = values[0];
+    let (s_d, s_u) = (spot_at(1, 0), spot_at(1, 1));
+    let (s_dd, s_ud, s_uu) = (spot_at(2, 0), spot_at(2, 1), spot_at(2, 2));
+
+    let delta = (step_one[1] - step_one[0]) / (s_u - s_d);
+    let delta_up = (step_two[2] - step_two[1]) / (s_uu - s_ud);
+    let delta_down = (step_two[1] - step_two[0]) / (s_ud - s_dd);
+    let gamma = (delta_up - delta_down) / (0.5 * (s_uu - s_dd));
+    // Leisen-Reimer trees do not recombine onto the spot, so strip the
+    // delta/gamma move between the root and the middle node at step two.
+    let ds = s_ud - s;
+    let theta = (step_two[1] - price - delta * ds - 0.5 * gamma * ds * ds) / (2.0 * dt) / basis.days_per_year();
+
+    TreeResult { price, delta, gamma, theta }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::black_scholes::black_scholes_price;
+    use crate::models::payoff::{OptionType, Vanilla};
+
+    #[test]
+    fn leisen_reimer_converges_to_black_scholes() {
+        for option_type in [OptionType::Call, OptionType::Put] {
+            let payoff = Vanilla::new(option_type, 95.0);
+            let tree = binomial_option_price(100.0, 0.75, 0.04, 0.01, 0.25, &payoff, &ExerciseStyle::European, 201, TreeMethod::LeisenReimer, TimeBasis::Calendar);
+            let exact = black_scholes_price(100.0, 95.0, 0.75, 0.04, 0.01, 0.25, option_type);
+            assert!((tree.price - exact).abs() < 1e-4, "{} tree {} vs {}", option_type, tree.price, exact);
+        }
+    }
+
+    #[test]
+    fn short_trees_still_report_greeks() {
+        let payoff = Vanilla::new(OptionType::Put, 100.0);
+        for method in [TreeMethod::CoxRossRubinstein, TreeMethod::LeisenReimer] {
+            for steps in [1, 2, 3] {
+                let tree = binomial_option_price(100.0, 0.5, 0.03, 0.0, 0.2, &payoff, &ExerciseStyle::American, steps, method, TimeBasis::Calendar);
+                assert!(tree.price.is_finite() && tree.delta.is_finite() && tree.gamma.is_finite() && tree.theta.is_finite());
+                assert!(tree.delta < 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn american_put_is_worth_at_least_european() {
+        let payoff = Vanilla::new(OptionType::Put, 110.0);
+        let american = binomial_option_price(100.0, 1.0, 0.05, 0.0, 0.2, &payoff, &ExerciseStyle::American, 301, TreeMethod::LeisenReimer, TimeBasis::Calendar);
+        let european = binomial_option_price(100.0, 1.0, 0.05, 0.0, 0.2, &payoff, &ExerciseStyle::European, 301, TreeMethod::LeisenReimer, TimeBasis::Calendar);
+        assert!(american.price > european.price);
+        assert!(american.price >= 10.0);
+    }
+}
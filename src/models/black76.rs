@@ -0,0 +1,86 @@
+use crate::models::black_scholes::{norm_cdf, norm_pdf};
+use crate::models::implied_vol::{implied_volatility, ImpliedVolError};
+use crate::models::payoff::OptionType;
+use crate::models::calendar::TimeBasis;
+
+// Sensitivities to the forward, in the units of the Black-Scholes suite: vega
+// per 1% vol (per basis point, 0.0001, of normal vol under Bachelier), theta
+// per day of the time basis and rho per 1% rate with the forward held fixed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ForwardGreeks {
+    pub price: f64,
+    pub delta: f64,
+    pub gamma: f64,
+    pub vega: f64,
+    pub theta: f64,
+    pub rho: f64,
+}
+
+fn d1_d2(f: f64, k: f64, t: f64, sigma: f64) -> (f64, f64) {
+    let d1 = ((f / k).ln() + 0.5 * sigma * sigma * t) / (sigma * t.sqrt());
+    (d1, d1 - sigma * t.sqrt())
+}
+
+pub fn black76_price(f: f64, k: f64, t: f64, r: f64, sigma: f64, option_type: OptionType) -> f64 {
+    let (d1, d2) = d1_d2(f, k, t, sigma);
+    let phi = option_type.sign();
+    (-r * t).exp() * phi * (f * norm_cdf(phi * d1) - k * norm_cdf(phi * d2))
+}
+
+pub fn black76_greeks(f: f64, k: f64, t: f64, r: f64, sigma: f64, option_type: OptionType, basis: TimeBasis) -> ForwardGreeks {
+    let (d1, _) = d1_d2(f, k, t, sigma);
+    let phi = option_type.sign();
+    let discount = (-r * t).exp();
+    let price = black76_price(f, k, t, r, sigma, option_type);
+    ForwardGreeks {
+        price,
+        delta: phi * discount * norm_cdf(phi * d1),
+        gamma: discount * norm_pdf(d1) / (f * sigma * t.sqrt()),
+        vega: discount * f * norm_pdf(d1) * t.sqrt() / 100.0,
+        theta: (-discount * f * norm_pdf(d1) * sigma / (2.0 * t.sqrt()) + r * price) / basis.days_per_year(),
+        rho: -t * price / 100.0,
+    }
+}
+
+// Black-76 is Black-Scholes with the spot replaced by the forward and the
+// dividend yield set to r, so the Black-Scholes solver inverts it exactly.
+pub fn black76_implied_vol(price: f64, f: f64, k: f64, t: f64, r: f64, option_type: OptionType) -> Result<f64, ImpliedVolError> {
+    implied_volatility(price, f, k, t, r, r, option_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::greeks::black_scholes_greeks;
+
+    #[test]
+    fn matches_black_scholes_on_the_forward() {
+        let (s, k, t, r, q, sigma): (f64, f64, f64, f64, f64, f64) = (100.0, 105.0, 0.5, 0.03, 0.01, 0.3);
+        let f = s * ((r - q) * t).exp();
+        for option_type in [OptionType::Call, OptionType::Put] {
+            let black = black76_greeks(f, k, t, r, sigma, option_type, TimeBasis::Calendar);
+            let spot = black_scholes_greeks(s, k, t, r, q, sigma, option_type, TimeBasis::Calendar);
+            assert!((black.price - spot.price).abs() < 1e-12);
+            assert!((black.vega - spot.vega).abs() < 1e-12);
+            assert!((black.delta * f - spot.delta * s).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn rho_and_vega_are_per_percent() {
+        let (f, k, t, r, sigma) = (100.0, 95.0, 1.0, 0.04, 0.2);
+        let greeks = black76_greeks(f, k, t, r, sigma, OptionType::Put, TimeBasis::Calendar);
+        let vega = black76_price(f, k, t, r, sigma + 1e-5, OptionType::Put) - black76_price(f, k, t, r, sigma - 1e-5, OptionType::Put);
+        let rho = black76_price(f, k, t, r + 1e-5, sigma, OptionType::Put) - black76_price(f, k, t, r - 1e-5, sigma, OptionType::Put);
+        assert!((greeks.vega - vega / 2e-5 / 100.0).abs() < 1e-7);
+        assert!((greeks.rho - rho / 2e-5 / 100.0).abs() < 1e-7);
+    }
+
+    #[test]
+    fn implied_vol_round_trip() {
+        let (f, k, t, r) = (100.0, 120.0, 2.0, 0.02);
+        let price = black76_price(f, k, t, r, 0.35, OptionType::Call);
+        let sigma = black76_implied_vol(price, f, k, t, r, OptionType::Call).unwrap();
+        assert!((sigma - 0.35).abs() < 1e-8);
+    }
+}
@@ -1,17 +1,74 @@
 use statrs::function::erf::erfc;
+use nalgebra::Complex;
+use crate::models::dividends::{CashDividend, escrowed_spot};
+use crate::models::fourier::CharacteristicFunction;
+use crate::models::payoff::OptionType;
 
-fn norm_cdf(x: f64) -> f64 {
+pub fn norm_cdf(x: f64) -> f64 {
     0.5 * erfc(-x / (2.0_f64).sqrt())
 }
 
-pub fn black_scholes_call(s: f64, k: f64, t: f64, r: f64, sigma: f64) -> f64 {
-    let d1 = (f64::ln(s / k) + (r + 0.5 * sigma * sigma) * t) / (sigma * f64::sqrt(t));
+pub fn norm_pdf(x: f64) -> f64 {
+    (-0.5 * x * x).exp() / (2.0 * std::f64::consts::PI).sqrt()
+}
+
+pub fn black_scholes_call(s: f64, k: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    let d1 = (f64::ln(s / k) + (r - q + 0.5 * sigma * sigma) * t) / (sigma * f64::sqrt(t));
     let d2 = d1 - sigma * f64::sqrt(t);
-    s * norm_cdf(d1) - k * f64::exp(-r * t) * norm_cdf(d2)
+    s * f64::exp(-q * t) * norm_cdf(d1) - k * f64::exp(-r * t) * norm_cdf(d2)
 }
 
-pub fn black_scholes_put(s: f64, k: f64, t: f64, r: f64, sigma: f64) -> f64 {
-    let d1 = (f64::ln(s / k) + (r + 0.5 * sigma * sigma) * t) / (sigma * f64::sqrt(t));
+pub fn black_scholes_put(s: f64, k: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    let d1 = (f64::ln(s / k) + (r - q + 0.5 * sigma * sigma) * t) / (sigma * f64::sqrt(t));
     let d2 = d1 - sigma * f64::sqrt(t);
-    k * f64::exp(-r * t) * norm_cdf(-d2) - s * norm_cdf(-d1)
+    k * f64::exp(-r * t) * norm_cdf(-d2) - s * f64::exp(-q * t) * norm_cdf(-d1)
+}
+
+pub fn black_scholes_price(s: f64, k: f64, t: f64, r: f64, q: f64, sigma: f64, option_type: OptionType) -> f64 {
+    match option_type {
+        OptionType::Call => black_scholes_call(s, k, t, r, q, sigma),
+        OptionType::Put => black_scholes_put(s, k, t, r, q, sigma),
+    }
+}
+
+pub fn black_scholes_vega(s: f64, k: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    let d1 = (f64::ln(s / k) + (r - q + 0.5 * sigma * sigma) * t) / (sigma * f64::sqrt(t));
+    s * f64::exp(-q * t) * norm_pdf(d1) * f64::sqrt(t)
+}
+
+pub fn black_scholes_call_with_dividends(
+    s: f64,
+    k: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    dividends: &[CashDividend],
+) -> f64 {
+    black_scholes_call(escrowed_spot(s, t, r, dividends), k, t, r, q, sigma)
+}
+
+pub fn black_scholes_put_with_dividends(
+    s: f64,
+    k: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    dividends: &[CashDividend],
+) -> f64 {
+    black_scholes_put(escrowed_spot(s, t, r, dividends), k, t, r, q, sigma)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlackScholesModel {
+    pub sigma: f64,
+}
+
+impl CharacteristicFunction for BlackScholesModel {
+    fn characteristic_function(&self, u: Complex<f64>, t: f64) -> Complex<f64> {
+        let i = Complex::new(0.0, 1.0);
+        let variance = self.sigma * self.sigma * t;
+        (-0.5 * variance * (i * u + u * u)).exp()
+    }
 }
@@ -0,0 +1,294 @@
+use std::collections::HashSet;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const CALENDAR_DAYS_PER_YEAR: f64 = 365.0;
+pub const TRADING_DAYS_PER_YEAR: f64 = 252.0;
+
+// Proleptic Gregorian calendar date. Field order makes the derived ordering
+// chronological.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+pub fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl Date {
+    pub fn new(year: i32, month: u32, day: u32) -> Result<Self, Box<dyn std::error::Error>> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return Err(format!("{}-{:02}-{:02} is not a valid date", year, month, day).into());
+        }
+        Ok(Date { year, month, day })
+    }
+
+    // UTC date of a Unix timestamp.
+    pub fn from_unix(seconds: u64) -> Self {
+        Date::from_days((seconds / 86_400) as i64)
+    }
+
+    pub fn today() -> Self {
+        Date::from_unix(SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs())
+    }
+
+    // Days since 1970-01-01 (Hinnant's days_from_civil).
+    pub fn to_days(&self) -> i64 {
+        let year = if self.month <= 2 { self.year as i64 - 1 } else { self.year as i64 };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let day_of_year = (153 * ((self.month as i64 + 9) % 12) + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    pub fn from_days(days: i64) -> Self {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let day_of_era = z - era * 146_097;
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+        let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 } as u32;
+        let year = (year_of_era + era * 400 + if month <= 2 { 1 } else { 0 }) as i32;
+        Date { year, month, day }
+    }
+
+    pub fn add_days(&self, days: i64) -> Self {
+        Date::from_days(self.to_days() + days)
+    }
+
+    // 0 = Monday through 6 = Sunday.
+    pub fn weekday(&self) -> u32 {
+        (self.to_days() + 3).rem_euclid(7) as u32
+    }
+
+    pub fn is_weekend(&self) -> bool {
+        self.weekday() >= 5
+    }
+}
+
+// The n-th given weekday of a month, counting from 1.
+fn nth_weekday(year: i32, month: u32, weekday: u32, n: u32) -> Date {
+    let first = Date { year, month, day: 1 };
+    let offset = (weekday + 7 - first.weekday()) % 7;
+    Date { year, month, day: 1 + offset + 7 * (n - 1) }
+}
+
+fn last_weekday(year: i32, month: u32, weekday: u32) -> Date {
+    let last = Date { year, month, day: days_in_month(year, month) };
+    let offset = (last.weekday() + 7 - weekday) % 7;
+    Date { year, month, day: last.day - offset }
+}
+
+// Anonymous Gregorian computus.
+fn easter_sunday(year: i32) -> Date {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    Date { year, month: month as u32, day: day as u32 }
+}
+
+// Saturday holidays are observed on the Friday before, Sunday ones on the
+// Monday after.
+fn observed(date: Date) -> Date {
+    match date.weekday() {
+        5 => date.add_days(-1),
+        6 => date.add_days(1),
+        _ => date,
+    }
+}
+
+// Regular NYSE full-day closures. A Saturday New Year's Day is not observed,
+// and one-off closures (national days of mourning, weather) are not modelled.
+pub fn nyse_holidays(year: i32) -> Vec<Date> {
+    let mut holidays = Vec::with_capacity(10);
+    let new_year = Date { year, month: 1, day: 1 };
+    if new_year.weekday() != 5 {
+        holidays.push(observed(new_year));
+    }
+    if year >= 1998 {
+        holidays.push(nth_weekday(year, 1, 0, 3));
+    }
+    holidays.push(nth_weekday(year, 2, 0, 3));
+    holidays.push(easter_sunday(year).add_days(-2));
+    holidays.push(last_weekday(year, 5, 0));
+    if year >= 2022 {
+        holidays.push(observed(Date { year, month: 6, day: 19 }));
+    }
+    holidays.push(observed(Date { year, month: 7, day: 4 }));
+    holidays.push(nth_weekday(year, 9, 0, 1));
+    holidays.push(nth_weekday(year, 11, 3, 4));
+    holidays.push(observed(Date { year, month: 12, day: 25 }));
+    holidays
+}
+
+pub fn is_nyse_business_day(date: Date) -> bool {
+    !date.is_weekend() && !nyse_holidays(date.year).contains(&date)
+}
+
+// NYSE business days in (start, end], negative when end precedes start.
+pub fn business_days_between(start: Date, end: Date) -> i64 {
+    if end < start {
+        return -business_days_between(end, start);
+    }
+    let mut holidays: HashSet<Date> = HashSet::new();
+    let mut loaded_year = None;
+    let mut count = 0;
+    for days in start.to_days() + 1..=end.to_days() {
+        let date = Date::from_days(days);
+        if loaded_year != Some(date.year) {
+            holidays = nyse_holidays(date.year).into_iter().collect();
+            loaded_year = Some(date.year);
+        }
+        if !date.is_weekend() && !holidays.contains(&date) {
+            count += 1;
+        }
+    }
+    count
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DayCount {
+    Act365Fixed,
+    Act360,
+    // ISDA: days falling in leap years over 366, the rest over 365
+    ActAct,
+    // US bond basis
+    Thirty360,
+    // NYSE business days over 252
+    Business252,
+}
+
+pub fn year_fraction(start: Date, end: Date, day_count: DayCount) -> f64 {
+    if end < start {
+        return -year_fraction(end, start, day_count);
+    }
+    let actual_days = (end.to_days() - start.to_days()) as f64;
+    match day_count {
+        DayCount::Act365Fixed => actual_days / 365.0,
+        DayCount::Act360 => actual_days / 360.0,
+        DayCount::ActAct => (start.year..=end.year)
+            .map(|year| {
+                let from = start.max(Date { year, month: 1, day: 1 });
+                let to = end.min(Date { year: year + 1, month: 1, day: 1 });
+                let basis = if is_leap_year(year) { 366.0 } else { 365.0 };
+                (to.to_days() - from.to_days()).max(0) as f64 / basis
+            })
+            .sum(),
+        DayCount::Thirty360 => {
+            let d1 = start.day.min(30) as i64;
+            let d2 = if end.day == 31 && d1 == 30 { 30 } else { end.day as i64 };
+            let days = 360 * (end.year - start.year) as i64 + 30 * (end.month as i64 - start.month as i64) + d2 - d1;
+            days as f64 / 360.0
+        }
+        DayCount::Business252 => business_days_between(start, end) as f64 / TRADING_DAYS_PER_YEAR,
+    }
+}
+
+// Clock that option time runs on: calendar time (ACT/365F), or trading time,
+// where only NYSE sessions age the option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeBasis {
+    Calendar,
+    Trading,
+}
+
+impl TimeBasis {
+    pub fn day_count(&self) -> DayCount {
+        match self {
+            TimeBasis::Calendar => DayCount::Act365Fixed,
+            TimeBasis::Trading => DayCount::Business252,
+        }
+    }
+
+    pub fn days_per_year(&self) -> f64 {
+        match self {
+            TimeBasis::Calendar => CALENDAR_DAYS_PER_YEAR,
+            TimeBasis::Trading => TRADING_DAYS_PER_YEAR,
+        }
+    }
+
+    pub fn year_fraction(&self, start: Date, end: Date) -> f64 {
+        year_fraction(start, end, self.day_count())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> Date {
+        Date::new(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn nyse_holidays_2025() {
+        let expected = [
+            date(2025, 1, 1),
+            date(2025, 1, 20),
+            date(2025, 2, 17),
+            date(2025, 4, 18),
+            date(2025, 5, 26),
+            date(2025, 6, 19),
+            date(2025, 7, 4),
+            date(2025, 9, 1),
+            date(2025, 11, 27),
+            date(2025, 12, 25),
+        ];
+        assert_eq!(nyse_holidays(2025), expected);
+    }
+
+    #[test]
+    fn weekend_holidays_are_observed() {
+        // July 4th 2026 is a Saturday, Christmas 2027 a Saturday, New Year 2022 a Saturday
+        assert!(nyse_holidays(2026).contains(&date(2026, 7, 3)));
+        assert!(nyse_holidays(2027).contains(&date(2027, 12, 24)));
+        assert!(!nyse_holidays(2021).contains(&date(2021, 12, 31)));
+        assert!(is_nyse_business_day(date(2021, 12, 31)));
+    }
+
+    #[test]
+    fn day_round_trip_and_weekday() {
+        for days in [-719_468, -1, 0, 59, 10_957, 20_000, 1_000_000] {
+            assert_eq!(Date::from_days(days).to_days(), days);
+        }
+        assert_eq!(Date::from_unix(0), date(1970, 1, 1));
+        assert_eq!(date(2024, 2, 29).weekday(), 3);
+        assert!(Date::new(2023, 2, 29).is_err());
+    }
+
+    #[test]
+    fn year_fractions() {
+        let (start, end) = (date(2023, 12, 15), date(2024, 6, 30));
+        assert!((year_fraction(start, end, DayCount::Act365Fixed) - 198.0 / 365.0).abs() < 1e-12);
+        assert!((year_fraction(start, end, DayCount::Act360) - 198.0 / 360.0).abs() < 1e-12);
+        assert!((year_fraction(start, end, DayCount::ActAct) - (17.0 / 365.0 + 181.0 / 366.0)).abs() < 1e-12);
+        assert!((year_fraction(date(2024, 1, 31), date(2024, 3, 31), DayCount::Thirty360) - 60.0 / 360.0).abs() < 1e-12);
+        assert_eq!(business_days_between(date(2024, 12, 31), date(2025, 12, 31)), 251);
+        assert!((year_fraction(end, start, DayCount::Act365Fixed) + 198.0 / 365.0).abs() < 1e-12);
+    }
+}
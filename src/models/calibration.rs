@@ -0,0 +1,36 @@
+use crate::models::payoff::OptionType;
+
+// A market implied vol to calibrate against. `r` is the zero rate to the
+// quote's own maturity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CalibrationQuote {
+    pub strike: f64,
+    pub t: f64,
+    pub r: f64,
+    pub implied_vol: f64,
+}
+
+impl CalibrationQuote {
+    // Quotes are fitted on their out-of-the-money side: puts below the
+    // forward, calls at and above it.
+    pub fn out_of_the_money_type(&self, s: f64, q: f64) -> OptionType {
+        if self.strike < s * ((self.r - q) * self.t).exp() {
+            OptionType::Put
+        } else {
+            OptionType::Call
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn puts_below_the_forward_and_calls_above() {
+        let quote = CalibrationQuote { strike: 102.0, t: 1.0, r: 0.04, implied_vol: 0.2 };
+        // the forward is 103.05, so a strike above spot can still be a put
+        assert_eq!(quote.out_of_the_money_type(100.0, 0.01), OptionType::Put);
+        assert_eq!(CalibrationQuote { strike: 104.0, ..quote }.out_of_the_money_type(100.0, 0.01), OptionType::Call);
+    }
+}
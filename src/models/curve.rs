@@ -0,0 +1,300 @@
+// Discount curves in continuously compounded zero rates; t in years.
+pub trait DiscountCurve {
+    fn discount_factor(&self, t: f64) -> f64;
+
+    fn zero_rate(&self, t: f64) -> f64 {
+        let t = t.max(1e-8);
+        -self.discount_factor(t).ln() / t
+    }
+
+    // Simply compounded forward rate for the accrual period [t1, t2].
+    fn forward_rate(&self, t1: f64, t2: f64) -> f64 {
+        (self.discount_factor(t1) / self.discount_factor(t2) - 1.0) / (t2 - t1)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlatCurve {
+    pub rate: f64,
+}
+
+impl FlatCurve {
+    pub fn new(rate: f64) -> Self {
+        FlatCurve { rate }
+    }
+}
+
+impl DiscountCurve for FlatCurve {
+    fn discount_factor(&self, t: f64) -> f64 {
+        (-self.rate * t).exp()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurveInterpolation {
+    LinearZero,
+    LogLinearDiscount,
+    // Hagan-West (2006): continuous forwards that preserve the discrete
+    // forwards between pillars and stay monotone where they are
+    MonotoneConvex,
+}
+
+// Zero rates at pillar maturities. Zero rates are flat beyond the last pillar
+// and, except under monotone convex, before the first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZeroCurve {
+    pub times: Vec<f64>,
+    pub zero_rates: Vec<f64>,
+    pub interpolation: CurveInterpolation,
+}
+
+impl ZeroCurve {
+    pub fn new(times: Vec<f64>, zero_rates: Vec<f64>, interpolation: CurveInterpolation) -> Result<Self, Box<dyn std::error::Error>> {
+        if times.is_empty() || times.len() != zero_rates.len() {
+            return Err("Need matching, non-empty pillar times and zero rates".into());
+        }
+        if times[0] <= 0.0 || times.windows(2).any(|w| w[1] <= w[0]) {
+            return Err("Pillar times must be positive and strictly increasing".into());
+        }
+        Ok(ZeroCurve { times, zero_rates, interpolation })
+    }
+
+    fn linear_zero(&self, t: f64) -> f64 {
+        let i = self.times.partition_point(|&x| x <= t);
+        let w = (t - self.times[i - 1]) / (self.times[i] - self.times[i - 1]);
+        self.zero_rates[i - 1] + w * (self.zero_rates[i] - self.zero_rates[i - 1])
+    }
+
+    fn log_linear_discount(&self, t: f64) -> f64 {
+        let i = self.times.partition_point(|&x| x <= t);
+        let (t0, t1) = (self.times[i - 1], self.times[i]);
+        let (y0, y1) = (self.zero_rates[i - 1] * t0, self.zero_rates[i] * t1);
+        (y0 + (t - t0) / (t1 - t0) * (y1 - y0)) / t
+    }
+
+    fn monotone_convex(&self, t: f64) -> f64 {
+        let n = self.times.len();
+        let mut times = vec![0.0];
+        times.extend_from_slice(&self.times);
+        let integrals: Vec<f64> = std::iter::once(0.0)
+            .chain(self.times.iter().zip(self.zero_rates.iter()).map(|(t, r)| t * r))
+            .collect();
+        let discrete: Vec<f64> = (1..=n)
+            .map(|i| (integrals[i] - integrals[i - 1]) / (times[i] - times[i - 1]))
+            .collect();
+        if n == 1 {
+            return discrete[0];
+        }
+
+        let mut nodes = vec![0.0; n + 1];
+        for i in 1..n {
+            nodes[i] = ((times[i] - times[i - 1]) * discrete[i] + (times[i + 1] - times[i]) * discrete[i - 1])
+                / (times[i + 1] - times[i - 1]);
+        }
+        nodes[0] = discrete[0] - 0.5 * (nodes[1] - discrete[0]);
+        nodes[n] = discrete[n - 1] - 0.5 * (nodes[n - 1] - discrete[n - 1]);
+
+        let i = times.partition_point(|&x| x <= t).min(n);
+        let width = times[i] - times[i - 1];
+        let x = (t - times[i - 1]) / width;
+        let g0 = nodes[i - 1] - discrete[i - 1];
+        let g1 = nodes[i] - discrete[i - 1];
+        (integrals[i - 1] + width * (discrete[i - 1] * x + monotone_convex_integral(g0, g1, x))) / t
+    }
+}
+
+// Integral over [0, x] of the Hagan-West correction g to the discrete forward
+// on a unit interval, with g(0) = g0 and g(1) = g1, split by their regions.
+fn monotone_convex_integral(g0: f64, g1: f64, x: f64) -> f64 {
+    if g0 * g1 == 0.0
+        || (g0 < 0.0 && -0.5 * g0 <= g1 && g1 <= -2.0 * g0)
+        || (g0 > 0.0 && -0.5 * g0 >= g1 && g1 >= -2.0 * g0)
+    {
+        g0 * (x - 2.0 * x * x + x.powi(3)) + g1 * (x.powi(3) - x * x)
+    } else if (g0 < 0.0 && g1 > -2.0 * g0) || (g0 > 0.0 && g1 < -2.0 * g0) {
+        let eta = (g1 + 2.0 * g0) / (g1 - g0);
+        if x <= eta {
+            g0 * x
+        } else {
+            g0 * x + (g1 - g0) * (x - eta).powi(3) / (3.0 * (1.0 - eta).powi(2))
+        }
+    } else if (g0 > 0.0 && g1 < 0.0) || (g0 < 0.0 && g1 > 0.0) {
+        let eta = 3.0 * g1 / (g1 - g0);
+        if x < eta {
+            g1 * x + (g0 - g1) * (eta.powi(3) - (eta - x).powi(3)) / (3.0 * eta * eta)
+        } else {
+            g1 * x + (g0 - g1) * eta / 3.0
+        }
+    } else {
+        let eta = g1 / (g1 + g0);
+        let a = -g0 * g1 / (g0 + g1);
+        if x <= eta {
+            a * x + (g0 - a) * (eta.powi(3) - (eta - x).powi(3)) / (3.0 * eta * eta)
+        } else {
+            a * x + (g0 - a) * eta / 3.0 + (g1 - a) * (x - eta).powi(3) / (3.0 * (1.0 - eta).powi(2))
+        }
+    }
+}
+
+impl DiscountCurve for ZeroCurve {
+    fn discount_factor(&self, t: f64) -> f64 {
+        (-self.zero_rate(t) * t).exp()
+    }
+
+    fn zero_rate(&self, t: f64) -> f64 {
+        let n = self.times.len();
+        if t >= self.times[n - 1] {
+            return self.zero_rates[n - 1];
+        }
+        if self.interpolation == CurveInterpolation::MonotoneConvex {
+            return self.monotone_convex(t.max(1e-8));
+        }
+        if t <= self.times[0] {
+            return self.zero_rates[0];
+        }
+        match self.interpolation {
+            CurveInterpolation::LinearZero => self.linear_zero(t),
+            CurveInterpolation::LogLinearDiscount => self.log_linear_discount(t),
+            CurveInterpolation::MonotoneConvex => unreachable!(),
+        }
+    }
+}
+
+// Bootstraps zero rates from constant-maturity par yields (maturity in years,
+// yield as a decimal, bond-equivalent basis). Bills up to one year are zero
+// coupon: simple yield to six months, semiannual compounding beyond. Longer
+// maturities are par bonds paying semiannual coupons, solved pillar by pillar
+// with coupon dates priced off the interpolated curve. Monotone convex
+// couples neighbouring pillars, so the passes repeat until the rates settle.
+pub fn bootstrap_zero_curve(par_yields: &[(f64, f64)], interpolation: CurveInterpolation) -> Result<ZeroCurve, Box<dyn std::error::Error>> {
+    let mut quotes: Vec<(f64, f64)> = par_yields.iter().cloned().filter(|(t, y)| *t > 0.0 && y.is_finite()).collect();
+    quotes.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    quotes.dedup_by(|a, b| (a.0 - b.0).abs() < 1e-12);
+    if quotes.is_empty() {
+        return Err("Need at least one par yield to bootstrap a curve".into());
+    }
+
+    let times: Vec<f64> = quotes.iter().map(|(t, _)| *t).collect();
+    let mut zero_rates: Vec<f64> = quotes
+        .iter()
+        .map(|&(t, y)| if t <= 0.5 { (1.0 + y * t).ln() / t } else { 2.0 * (1.0 + 0.5 * y).ln() })
+        .collect();
+
+    for _ in 0..50 {
+        let previous = zero_rates.clone();
+        for (i, &(maturity, par_yield)) in quotes.iter().enumerate() {
+            if maturity <= 1.0 {
+                continue;
+            }
+            let coupon_dates: Vec<f64> = (0..)
+                .map(|k| maturity - 0.5 * k as f64)
+                .take_while(|&date| date > 1e-9)
+                .collect();
+            let par_error = |rate: f64, zero_rates: &mut Vec<f64>| {
+                zero_rates[i] = rate;
+                let curve = ZeroCurve { times: times.clone(), zero_rates: zero_rates.clone(), interpolation };
+                let coupons: f64 = coupon_dates.iter().map(|&date| curve.discount_factor(date)).sum();
+                0.5 * par_yield * coupons + curve.discount_factor(maturity) - 1.0
+            };
+
+            // price is decreasing in the pillar rate
+            let (mut lo, mut hi) = (-0.5, 1.0);
+            for _ in 0..200 {
+                let mid = 0.5 * (lo + hi);
+                if par_error(mid, &mut zero_rates) > 0.0 {
+                    lo = mid;
+                } else {
+                    hi = mid;
+                }
+                if hi - lo < 1e-14 {
+                    break;
+                }
+            }
+            zero_rates[i] = 0.5 * (lo + hi);
+        }
+
+        let change = zero_rates.iter().zip(previous.iter()).map(|(a, b)| (a - b).abs()).fold(0.0, f64::max);
+        if change < 1e-12 {
+            break;
+        }
+    }
+
+    ZeroCurve::new(times, zero_rates, interpolation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTERPOLATIONS: [CurveInterpolation; 3] =
+        [CurveInterpolation::LinearZero, CurveInterpolation::LogLinearDiscount, CurveInterpolation::MonotoneConvex];
+
+    // A humped curve with an inversion, as in the Treasury par yields
+    fn par_yields() -> Vec<(f64, f64)> {
+        vec![
+            (1.0 / 12.0, 0.0530), (0.25, 0.0535), (0.5, 0.0528), (1.0, 0.0500), (2.0, 0.0460), (3.0, 0.0440),
+            (5.0, 0.0420), (7.0, 0.0425), (10.0, 0.0430), (20.0, 0.0460), (30.0, 0.0445),
+        ]
+    }
+
+    // Dirty price of each input instrument off the curve, per unit notional.
+    fn price(curve: &ZeroCurve, maturity: f64, par_yield: f64) -> f64 {
+        if maturity <= 0.5 {
+            curve.discount_factor(maturity) * (1.0 + par_yield * maturity)
+        } else if maturity <= 1.0 {
+            curve.discount_factor(maturity) * (1.0 + 0.5 * par_yield).powf(2.0 * maturity)
+        } else {
+            let coupons: f64 = (0..)
+                .map(|k| maturity - 0.5 * k as f64)
+                .take_while(|&date| date > 1e-9)
+                .map(|date| curve.discount_factor(date))
+                .sum();
+            0.5 * par_yield * coupons + curve.discount_factor(maturity)
+        }
+    }
+
+    #[test]
+    fn bootstrapped_curve_reprices_every_instrument() {
+        for interpolation in INTERPOLATIONS {
+            let curve = bootstrap_zero_curve(&par_yields(), interpolation).unwrap();
+            for (maturity, par_yield) in par_yields() {
+                let price = price(&curve, maturity, par_yield);
+                assert!((price - 1.0).abs() < 1e-10, "{interpolation:?} {maturity}: {price}");
+            }
+        }
+    }
+
+    #[test]
+    fn interpolation_passes_through_the_pillars() {
+        let times = vec![0.25, 0.5, 1.0, 2.0, 5.0, 10.0, 30.0];
+        let rates = vec![0.052, 0.053, 0.050, 0.046, 0.042, 0.043, 0.045];
+        for interpolation in INTERPOLATIONS {
+            let curve = ZeroCurve::new(times.clone(), rates.clone(), interpolation).unwrap();
+            for (&t, &rate) in times.iter().zip(rates.iter()) {
+                assert!((curve.zero_rate(t) - rate).abs() < 1e-12, "{interpolation:?} at {t}: {}", curve.zero_rate(t));
+                // and is continuous there
+                for bumped in [t * (1.0 - 1e-9), t * (1.0 + 1e-9)] {
+                    assert!((curve.zero_rate(bumped) - rate).abs() < 1e-8, "{interpolation:?} near {t}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn monotone_convex_forwards_stay_positive() {
+        let curve = bootstrap_zero_curve(&par_yields(), CurveInterpolation::MonotoneConvex).unwrap();
+        for i in 0..600 {
+            let t = 0.05 * i as f64;
+            let forward = curve.forward_rate(t, t + 0.01);
+            assert!(forward > 0.0 && forward < 0.1, "{t}: {forward}");
+        }
+    }
+
+    #[test]
+    fn invalid_pillars_are_errors() {
+        assert!(ZeroCurve::new(vec![], vec![], CurveInterpolation::LinearZero).is_err());
+        assert!(ZeroCurve::new(vec![1.0, 2.0], vec![0.05], CurveInterpolation::LinearZero).is_err());
+        assert!(ZeroCurve::new(vec![2.0, 1.0], vec![0.05, 0.05], CurveInterpolation::LinearZero).is_err());
+        assert!(bootstrap_zero_curve(&[], CurveInterpolation::LinearZero).is_err());
+    }
+}
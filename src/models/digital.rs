@@ -0,0 +1,118 @@
+use crate::models::black_scholes::norm_cdf;
+use crate::models::monte_carlo::european_monte_carlo_price;
+use crate::models::payoff::{CustomPayoff, Digital, OptionType};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DigitalType {
+    CashOrNothing(f64),
+    AssetOrNothing,
+}
+
+fn d1_d2(s: f64, k: f64, t: f64, r: f64, q: f64, sigma: f64) -> (f64, f64) {
+    let d1 = ((s / k).ln() + (r - q + 0.5 * sigma * sigma) * t) / (sigma * t.sqrt());
+    (d1, d1 - sigma * t.sqrt())
+}
+
+// Cash-or-nothing pays the fixed amount, asset-or-nothing pays S_T, whenever
+// the option finishes in the money.
+pub fn digital_option_price(s: f64, k: f64, t: f64, r: f64, q: f64, sigma: f64, option_type: OptionType, digital_type: DigitalType) -> f64 {
+    let (d1, d2) = d1_d2(s, k, t, r, q, sigma);
+    let sign = option_type.sign();
+    match digital_type {
+        DigitalType::CashOrNothing(cash) => cash * (-r * t).exp() * norm_cdf(sign * d2),
+        DigitalType::AssetOrNothing => s * (-q * t).exp() * norm_cdf(sign * d1),
+    }
+}
+
+// Gap option: pays S_T - k_payoff (call) or k_payoff - S_T (put) once S_T
+// crosses the trigger strike, so the payoff can be negative.
+pub fn gap_option_price(s: f64, k_payoff: f64, k_trigger: f64, t: f64, r: f64, q: f64, sigma: f64, option_type: OptionType) -> f64 {
+    let (d1, d2) = d1_d2(s, k_trigger, t, r, q, sigma);
+    match option_type {
+        OptionType::Call => s * (-q * t).exp() * norm_cdf(d1) - k_payoff * (-r * t).exp() * norm_cdf(d2),
+        OptionType::Put => k_payoff * (-r * t).exp() * norm_cdf(-d2) - s * (-q * t).exp() * norm_cdf(-d1),
+    }
+}
+
+pub fn digital_monte_carlo_price(
+    s: f64,
+    k: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    option_type: OptionType,
+    digital_type: DigitalType,
+    num_paths: usize,
+) -> f64 {
+    european_monte_carlo_price(s, t, r, q, sigma, &Digital::new(option_type, k, digital_type), num_paths)
+}
+
+pub fn gap_monte_carlo_price(
+    s: f64,
+    k_payoff: f64,
+    k_trigger: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    option_type: OptionType,
+    num_paths: usize,
+) -> f64 {
+    let sign = option_type.sign();
+    let gap = CustomPayoff::new(k_trigger, |spot: f64| {
+        if sign * (spot - k_trigger) > 0.0 { sign * (spot - k_payoff) } else { 0.0 }
+    });
+    european_monte_carlo_price(s, t, r, q, sigma, &gap, num_paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::black_scholes::black_scholes_price;
+
+    const S: f64 = 100.0;
+    const T: f64 = 0.75;
+    const R: f64 = 0.03;
+    const Q: f64 = 0.01;
+    const SIGMA: f64 = 0.25;
+
+    // Mean and standard error over independent batches.
+    fn batched<F: FnMut() -> f64>(batches: usize, mut price: F) -> (f64, f64) {
+        let samples: Vec<f64> = (0..batches).map(|_| price()).collect();
+        let mean = samples.iter().sum::<f64>() / batches as f64;
+        let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (batches - 1) as f64;
+        (mean, (variance / batches as f64).sqrt())
+    }
+
+    #[test]
+    fn digitals_match_the_closed_forms() {
+        for option_type in [OptionType::Call, OptionType::Put] {
+            for digital_type in [DigitalType::CashOrNothing(10.0), DigitalType::AssetOrNothing] {
+                let exact = digital_option_price(S, 105.0, T, R, Q, SIGMA, option_type, digital_type);
+                let (price, std_error) = batched(20, || digital_monte_carlo_price(S, 105.0, T, R, Q, SIGMA, option_type, digital_type, 10_000));
+                assert!((price - exact).abs() < 4.0 * std_error, "{option_type:?} {digital_type:?}: {price} +/- {std_error} vs {exact}");
+            }
+        }
+    }
+
+    #[test]
+    fn gaps_match_the_closed_forms() {
+        for option_type in [OptionType::Call, OptionType::Put] {
+            for (k_payoff, k_trigger) in [(100.0, 110.0), (110.0, 100.0)] {
+                let exact = gap_option_price(S, k_payoff, k_trigger, T, R, Q, SIGMA, option_type);
+                let (price, std_error) = batched(20, || gap_monte_carlo_price(S, k_payoff, k_trigger, T, R, Q, SIGMA, option_type, 10_000));
+                assert!((price - exact).abs() < 4.0 * std_error, "{option_type:?} {k_payoff}/{k_trigger}: {price} +/- {std_error} vs {exact}");
+            }
+        }
+    }
+
+    #[test]
+    fn cash_and_asset_digitals_replicate_the_vanilla() {
+        let cash = digital_option_price(S, 105.0, T, R, Q, SIGMA, OptionType::Call, DigitalType::CashOrNothing(105.0));
+        let asset = digital_option_price(S, 105.0, T, R, Q, SIGMA, OptionType::Call, DigitalType::AssetOrNothing);
+        let vanilla = black_scholes_price(S, 105.0, T, R, Q, SIGMA, OptionType::Call);
+        assert!((asset - cash - vanilla).abs() < 1e-10);
+        assert!((gap_option_price(S, 105.0, 105.0, T, R, Q, SIGMA, OptionType::Call) - vanilla).abs() < 1e-10);
+    }
+}
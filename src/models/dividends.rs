@@ -0,0 +1,30 @@
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CashDividend {
+    pub time: f64,
+    pub amount: f64,
+}
+
+impl CashDividend {
+    pub fn new(time: f64, amount: f64) -> Self {
+        CashDividend { time, amount }
+    }
+}
+
+// Value at `time` of the dividends still to be paid in (time, t].
+pub fn dividends_value_at(time: f64, t: f64, r: f64, dividends: &[CashDividend]) -> f64 {
+    dividends
+        .iter()
+        .filter(|d| d.time > time && d.time <= t)
+        .map(|d| d.amount * (-r * (d.time - time)).exp())
+        .sum()
+}
+
+// Escrowed-dividend model: the spot is reduced by the present value of every
+// cash dividend paid before expiry, and the remainder diffuses as a GBM.
+pub fn present_value_of_dividends(t: f64, r: f64, dividends: &[CashDividend]) -> f64 {
+    dividends_value_at(0.0, t, r, dividends)
+}
+
+pub fn escrowed_spot(s: f64, t: f64, r: f64, dividends: &[CashDividend]) -> f64 {
+    (s - present_value_of_dividends(t, r, dividends)).max(0.0)
+}
@@ -0,0 +1,325 @@
+use nalgebra::{Complex, DVector};
+use std::f64::consts::PI;
+use crate::models::black_scholes::{black_scholes_price, black_scholes_vega};
+use crate::models::calibration::CalibrationQuote;
+use crate::models::implied_vol::implied_volatility;
+use crate::models::optimize::levenberg_marquardt;
+use crate::models::payoff::OptionType;
+
+// Models priced by the Fourier engine expose the characteristic function of
+// X_T = ln(S_T / F_T), where F_T = S_0 e^{(r - q) T} is the forward, so that
+// E[e^{X_T}] = 1 and rates and dividends are handled by the engine.
+pub trait CharacteristicFunction {
+    fn characteristic_function(&self, u: Complex<f64>, t: f64) -> Complex<f64>;
+
+    // First two cumulants of X_T, by default from central differences of
+    // ln(phi) at the origin.
+    fn cumulants(&self, t: f64) -> (f64, f64) {
+        let h = 1e-3;
+        let log_phi = |u: f64| self.characteristic_function(Complex::new(u, 0.0), t).ln();
+        let (up, mid, down) = (log_phi(h), log_phi(0.0), log_phi(-h));
+        let c1 = ((up - down) / (2.0 * h)).im;
+        let c2 = -((up - 2.0 * mid + down) / (h * h)).re;
+        (c1, c2.max(1e-12))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FourierMethod {
+    CarrMadan,
+    Cos,
+}
+
+const CARR_MADAN_POINTS: usize = 4096;
+const CARR_MADAN_ETA: f64 = 0.25;
+const CARR_MADAN_ALPHA: f64 = 1.5;
+const COS_TERMS: usize = 256;
+const COS_TRUNCATION: f64 = 12.0;
+
+fn fft(values: &mut [Complex<f64>]) {
+    let n = values.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let angle = -2.0 * PI / len as f64;
+        let root = Complex::new(angle.cos(), angle.sin());
+        for start in (0..n).step_by(len) {
+            let mut w = Complex::new(1.0, 0.0);
+            for offset in 0..len / 2 {
+                let even = values[start + offset];
+                let odd = values[start + offset + len / 2] * w;
+                values[start + offset] = even + odd;
+                values[start + offset + len / 2] = even - odd;
+                w *= root;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+// Undiscounted calls on a forward of 1, returned on the log-strike grid.
+fn carr_madan_grid<M: CharacteristicFunction>(model: &M, t: f64) -> (Vec<f64>, Vec<f64>) {
+    let n = CARR_MADAN_POINTS;
+    let eta = CARR_MADAN_ETA;
+    let alpha = CARR_MADAN_ALPHA;
+    let lambda = 2.0 * PI / (n as f64 * eta);
+    let b = 0.5 * n as f64 * lambda;
+    let i = Complex::new(0.0, 1.0);
+
+    let mut values: Vec<Complex<f64>> = (0..n)
+        .map(|j| {
+            let v = j as f64 * eta;
+            let phi = model.characteristic_function(Complex::new(v, -(alpha + 1.0)), t);
+            let denominator = Complex::new(alpha * alpha + alpha - v * v, (2.0 * alpha + 1.0) * v);
+            let simpson = (3.0 + if j % 2 == 0 { -1.0 } else { 1.0 } - if j == 0 { 1.0 } else { 0.0 }) / 3.0;
+            (i * b * v).exp() * phi / denominator * eta * simpson
+        })
+        .collect();
+    fft(&mut values);
+
+    let log_strikes = (0..n).map(|u| -b + lambda * u as f64).collect();
+    let calls = values
+        .iter()
+        .enumerate()
+        .map(|(u, value)| (-alpha * (-b + lambda * u as f64)).exp() / PI * value.re)
+        .collect();
+    (log_strikes, calls)
+}
+
+fn interpolate(xs: &[f64], ys: &[f64], x: f64) -> f64 {
+    let i = xs.partition_point(|&xi| xi < x).clamp(1, xs.len() - 1);
+    let weight = (x - xs[i - 1]) / (xs[i] - xs[i - 1]);
+    ys[i - 1] + weight * (ys[i] - ys[i - 1])
+}
+
+fn cos_chi(k: f64, a: f64, b: f64, c: f64, d: f64) -> f64 {
+    let w = k * PI / (b - a);
+    let (sin_d, cos_d) = (w * (d - a)).sin_cos();
+    let (sin_c, cos_c) = (w * (c - a)).sin_cos();
+    (cos_d * d.exp() - cos_c * c.exp() + w * sin_d * d.exp() - w * sin_c * c.exp()) / (1.0 + w * w)
+}
+
+fn cos_psi(k: f64, a: f64, b: f64, c: f64, d: f64) -> f64 {
+    if k == 0.0 {
+        return d - c;
+    }
+    let w = k * PI / (b - a);
+    ((w * (d - a)).sin() - (w * (c - a)).sin()) / w
+}
+
+// Fang-Oosterlee COS method, pricing puts (which have bounded payoffs and are
+// far less sensitive to the truncation range) and recovering calls by parity.
+fn cos_puts<M: CharacteristicFunction>(model: &M, forward: f64, strikes: &[f64], t: f64) -> Vec<f64> {
+    let (c1, c2) = model.cumulants(t);
+    let a = c1 - COS_TRUNCATION * c2.sqrt();
+    let b = c1 + COS_TRUNCATION * c2.sqrt();
+
+    let terms: Vec<(Complex<f64>, f64)> = (0..COS_TERMS)
+        .map(|k| {
+            let kf = k as f64;
+            let phi = model.characteristic_function(Complex::new(kf * PI / (b - a), 0.0), t);
+            let coefficient = 2.0 / (b - a) * (-cos_chi(kf, a, b, a, 0.0) + cos_psi(kf, a, b, a, 0.0));
+            (phi, coefficient)
+        })
+        .collect();
+
+    strikes
+        .iter()
+        .map(|&strike| {
+            let x = (forward / strike).ln();
+            let sum: f64 = terms
+                .iter()
+                .enumerate()
+                .map(|(k, (phi, coefficient))| {
+                    let angle = k as f64 * PI * (x - a) / (b - a);
+                    let term = (phi * Complex::new(angle.cos(), angle.sin())).re * coefficient;
+                    if k == 0 { 0.5 * term } else { term }
+                })
+                .sum();
+            (strike * sum).max(0.0)
+        })
+        .collect()
+}
+
+pub fn fourier_price_strikes<M: CharacteristicFunction>(
+    model: &M,
+    s: f64,
+    strikes: &[f64],
+    t: f64,
+    r: f64,
+    q: f64,
+    option_type: OptionType,
+    method: FourierMethod,
+) -> Vec<f64> {
+    let forward = s * ((r - q) * t).exp();
+    let discount = (-r * t).exp();
+
+    let undiscounted_calls: Vec<f64> = match method {
+        FourierMethod::CarrMadan => {
+            let (log_strikes, calls) = carr_madan_grid(model, t);
+            strikes
+                .iter()
+                .map(|&strike| forward * interpolate(&log_strikes, &calls, (strike / forward).ln()).max(0.0))
+                .collect()
+        }
+        FourierMethod::Cos => cos_puts(model, forward, strikes, t)
+            .iter()
+            .zip(strikes.iter())
+            .map(|(put, &strike)| put + forward - strike)
+            .collect(),
+    };
+
+    undiscounted_calls
+        .iter()
+        .zip(strikes.iter())
+        .map(|(&call, &strike)| {
+            let price = match option_type {
+                OptionType::Call => call,
+                OptionType::Put => call - forward + strike,
+            };
+            discount * price.max(0.0)
+        })
+        .collect()
+}
+
+pub fn fourier_smile<M: CharacteristicFunction>(
+    model: &M,
+    s: f64,
+    strikes: &[f64],
+    t: f64,
+    r: f64,
+    q: f64,
+    method: FourierMethod,
+) -> Vec<(f64, f64)> {
+    let forward = s * ((r - q) * t).exp();
+    let calls = fourier_price_strikes(model, s, strikes, t, r, q, OptionType::Call, method);
+    strikes
+        .iter()
+        .zip(calls.iter())
+        .filter_map(|(&strike, &call)| {
+            // Out-of-the-money side carries the volatility information.
+            let (price, option_type) = if strike >= forward {
+                (call, OptionType::Call)
+            } else {
+                (call - (-r * t).exp() * (forward - strike), OptionType::Put)
+            };
+            implied_volatility(price, s, strike, t, r, q, option_type).ok().map(|iv| (strike, iv))
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelCalibration<P> {
+    pub params: P,
+    pub rmse_iv: f64,
+    pub iterations: usize,
+    pub converged: bool,
+}
+
+// Fits any characteristic-function model to a chain by Levenberg-Marquardt on
+// vega-weighted COS price errors. `build` maps the raw parameter vector onto
+// the model.
+pub fn calibrate_fourier_model<M: CharacteristicFunction, F: Fn(&DVector<f64>) -> M>(
+    s: f64,
+    q: f64,
+    quotes: &[CalibrationQuote],
+    build: F,
+    initial: DVector<f64>,
+    lower: &[f64],
+    upper: &[f64],
+    max_iterations: usize,
+) -> Result<ModelCalibration<DVector<f64>>, Box<dyn std::error::Error>> {
+    if quotes.len() < initial.len() {
+        return Err("Need at least as many quotes as model parameters".into());
+    }
+
+    let market_prices: Vec<f64> = quotes
+        .iter()
+        .map(|quote| black_scholes_price(s, quote.strike, quote.t, quote.r, q, quote.implied_vol, quote.out_of_the_money_type(s, q)))
+        .collect();
+    let vegas: Vec<f64> = quotes
+        .iter()
+        .map(|quote| black_scholes_vega(s, quote.strike, quote.t, quote.r, q, quote.implied_vol).max(1e-4))
+        .collect();
+
+    let residuals = |p: &DVector<f64>| {
+        let model = build(p);
+        DVector::from_iterator(
+            quotes.len(),
+            quotes.iter().enumerate().map(|(i, quote)| {
+                let option_type = quote.out_of_the_money_type(s, q);
+                let price = fourier_price_strikes(&model, s, &[quote.strike], quote.t, quote.r, q, option_type, FourierMethod::Cos)[0];
+                (price - market_prices[i]) / vegas[i]
+            }),
+        )
+    };
+
+    let fit = levenberg_marquardt(residuals, initial, lower, upper, max_iterations);
+    Ok(ModelCalibration {
+        rmse_iv: (fit.residuals.norm_squared() / quotes.len() as f64).sqrt(),
+        params: fit.params,
+        iterations: fit.iterations,
+        converged: fit.converged,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::black_scholes::{BlackScholesModel, black_scholes_price};
+
+    const S: f64 = 100.0;
+    const R: f64 = 0.04;
+    const Q: f64 = 0.01;
+    const SIGMA: f64 = 0.25;
+    const STRIKES: [f64; 7] = [70.0, 85.0, 95.0, 100.0, 105.0, 115.0, 140.0];
+
+    #[test]
+    fn black_scholes_matches_the_closed_form() {
+        let model = BlackScholesModel { sigma: SIGMA };
+        // Carr-Madan interpolates linearly on a log-strike grid about 0.6% apart,
+        // which costs a few tenths of a cent at the money on short expiries
+        for (method, tolerance) in [(FourierMethod::Cos, 1e-8), (FourierMethod::CarrMadan, 5e-3)] {
+            for t in [0.1, 1.0, 3.0] {
+                for option_type in [OptionType::Call, OptionType::Put] {
+                    let prices = fourier_price_strikes(&model, S, &STRIKES, t, R, Q, option_type, method);
+                    for (&k, &price) in STRIKES.iter().zip(prices.iter()) {
+                        let exact = black_scholes_price(S, k, t, R, Q, SIGMA, option_type);
+                        assert!((price - exact).abs() < tolerance, "{method:?} {option_type:?} t={t} k={k}: {price} vs {exact}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn black_scholes_smile_is_flat() {
+        let model = BlackScholesModel { sigma: SIGMA };
+        for (k, iv) in fourier_smile(&model, S, &STRIKES, 1.0, R, Q, FourierMethod::Cos) {
+            assert!((iv - SIGMA).abs() < 1e-6, "{k}: {iv}");
+        }
+    }
+
+    #[test]
+    fn calibration_recovers_black_scholes_vol() {
+        let quotes: Vec<CalibrationQuote> = STRIKES
+            .iter()
+            .map(|&strike| CalibrationQuote { strike, t: 1.0, r: R, implied_vol: SIGMA })
+            .collect();
+        let fit = calibrate_fourier_model(S, Q, &quotes, |p| BlackScholesModel { sigma: p[0] }, DVector::from_vec(vec![0.4]), &[0.01], &[2.0], 50).unwrap();
+        assert!((fit.params[0] - SIGMA).abs() < 1e-6, "{}", fit.params[0]);
+        assert!(fit.rmse_iv < 1e-6);
+    }
+}
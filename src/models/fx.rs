@@ -0,0 +1,245 @@
+use statrs::distribution::{Normal, ContinuousCDF};
+use crate::models::black_scholes::{black_scholes_price, norm_cdf, norm_pdf};
+use crate::models::payoff::OptionType;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeltaConvention {
+    Spot,
+    Forward,
+    PremiumAdjustedSpot,
+    PremiumAdjustedForward,
+}
+
+impl DeltaConvention {
+    pub fn is_premium_adjusted(&self) -> bool {
+        matches!(self, DeltaConvention::PremiumAdjustedSpot | DeltaConvention::PremiumAdjustedForward)
+    }
+}
+
+// Strike/vol pillars of an FX smile, ordered put wing, ATM, call wing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FxSmile {
+    pub forward: f64,
+    pub t: f64,
+    pub strikes: Vec<f64>,
+    pub vols: Vec<f64>,
+}
+
+impl FxSmile {
+    // Quadratic in log-moneyness through the three pillars, flat beyond the
+    // wings so the extrapolated vol cannot turn negative.
+    pub fn vol_at(&self, strike: f64) -> f64 {
+        let x: Vec<f64> = self.strikes.iter().map(|k| (k / self.forward).ln()).collect();
+        let xk = (strike / self.forward).ln().clamp(x[0], x[2]);
+        (0..3)
+            .map(|i| {
+                let basis: f64 = (0..3)
+                    .filter(|&j| j != i)
+                    .map(|j| (xk - x[j]) / (x[i] - x[j]))
+                    .product();
+                basis * self.vols[i]
+            })
+            .sum()
+    }
+}
+
+// Garman-Kohlhagen: Black-Scholes with the foreign rate as the yield. Spot is
+// quoted as domestic per unit of foreign and the premium is in domestic.
+pub fn garman_kohlhagen_price(s: f64, k: f64, t: f64, r_d: f64, r_f: f64, sigma: f64, option_type: OptionType) -> f64 {
+    black_scholes_price(s, k, t, r_d, r_f, sigma, option_type)
+}
+
+pub fn fx_delta(
+    s: f64,
+    k: f64,
+    t: f64,
+    r_d: f64,
+    r_f: f64,
+    sigma: f64,
+    option_type: OptionType,
+    convention: DeltaConvention,
+) -> f64 {
+    let forward = s * ((r_d - r_f) * t).exp();
+    let d1 = ((forward / k).ln() + 0.5 * sigma * sigma * t) / (sigma * t.sqrt());
+    let d2 = d1 - sigma * t.sqrt();
+    let phi = option_type.sign();
+    let foreign_discount = (-r_f * t).exp();
+    match convention {
+        DeltaConvention::Spot => phi * foreign_discount * norm_cdf(phi * d1),
+        DeltaConvention::Forward => phi * norm_cdf(phi * d1),
+        DeltaConvention::PremiumAdjustedSpot => phi * foreign_discount * k / forward * norm_cdf(phi * d2),
+        DeltaConvention::PremiumAdjustedForward => phi * k / forward * norm_cdf(phi * d2),
+    }
+}
+
+fn bisect<F: Fn(f64) -> f64>(f: F, mut lo: f64, mut hi: f64) -> f64 {
+    let f_lo = f(lo);
+    for _ in 0..200 {
+        let mid = 0.5 * (lo + hi);
+        if (f(mid) > 0.0) == (f_lo > 0.0) {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+        if hi - lo < 1e-14 {
+            break;
+        }
+    }
+    0.5 * (lo + hi)
+}
+
+// Inverts a quoted delta (positive for calls, negative for puts) into a
+// strike. Unadjusted deltas invert in closed form. Premium-adjusted deltas are
+// solved by bisection in log-strike: puts are monotone below the unadjusted
+// strike, while a premium-adjusted call delta peaks at some strike and the
+// quote refers to the branch above that peak.
+pub fn strike_from_delta(
+    delta: f64,
+    s: f64,
+    t: f64,
+    r_d: f64,
+    r_f: f64,
+    sigma: f64,
+    option_type: OptionType,
+    convention: DeltaConvention,
+) -> Result<f64, Box<dyn std::error::Error>> {
+    let phi = option_type.sign();
+    let foreign_discount = (-r_f * t).exp();
+    let forward_delta = match convention {
+        DeltaConvention::Spot | DeltaConvention::PremiumAdjustedSpot => delta / foreign_discount,
+        DeltaConvention::Forward | DeltaConvention::PremiumAdjustedForward => delta,
+    };
+    if !(phi * forward_delta > 0.0 && phi * forward_delta < 1.0) {
+        return Err(format!("Delta {} is out of range for a {}", delta, option_type).into());
+    }
+
+    let forward = s * ((r_d - r_f) * t).exp();
+    let vol_t = sigma * t.sqrt();
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let d1 = phi * normal.inverse_cdf(phi * forward_delta);
+    let unadjusted = forward * (-d1 * vol_t + 0.5 * vol_t * vol_t).exp();
+    if !convention.is_premium_adjusted() {
+        return Ok(unadjusted);
+    }
+
+    let residual = |log_k: f64| fx_delta(s, log_k.exp(), t, r_d, r_f, sigma, option_type, convention) - delta;
+    let lower = match option_type {
+        OptionType::Put => unadjusted.ln() - 10.0 * vol_t.max(0.1),
+        OptionType::Call => {
+            // peak of K N(d2): sigma sqrt(T) N(d2) = n(d2)
+            let d2_peak = bisect(|d2| vol_t * norm_cdf(d2) - norm_pdf(d2), -vol_t, 10.0);
+            let peak = forward * (-d2_peak * vol_t - 0.5 * vol_t * vol_t).exp();
+            if residual(peak.ln()) < 0.0 {
+                return Err(format!("Premium-adjusted call delta {} is above the attainable maximum", delta).into());
+            }
+            peak.ln()
+        }
+    };
+    Ok(bisect(residual, lower, unadjusted.ln()).exp())
+}
+
+// Builds the strike/vol smile from ATM, risk-reversal and butterfly quotes at
+// the given delta (0.25 for the usual 25-delta pillars). The ATM strike is the
+// delta-neutral straddle and the wings use the smile-strangle approximation
+// sigma = atm + bf +/- rr / 2.
+pub fn fx_smile_from_quotes(
+    s: f64,
+    t: f64,
+    r_d: f64,
+    r_f: f64,
+    atm_vol: f64,
+    risk_reversal: f64,
+    butterfly: f64,
+    delta: f64,
+    convention: DeltaConvention,
+) -> Result<FxSmile, Box<dyn std::error::Error>> {
+    let forward = s * ((r_d - r_f) * t).exp();
+    let atm_strike = if convention.is_premium_adjusted() {
+        forward * (-0.5 * atm_vol * atm_vol * t).exp()
+    } else {
+        forward * (0.5 * atm_vol * atm_vol * t).exp()
+    };
+
+    let call_vol = atm_vol + butterfly + 0.5 * risk_reversal;
+    let put_vol = atm_vol + butterfly - 0.5 * risk_reversal;
+    if call_vol <= 0.0 || put_vol <= 0.0 {
+        return Err("Risk reversal and butterfly imply a non-positive wing vol".into());
+    }
+    let call_strike = strike_from_delta(delta, s, t, r_d, r_f, call_vol, OptionType::Call, convention)?;
+    let put_strike = strike_from_delta(-delta, s, t, r_d, r_f, put_vol, OptionType::Put, convention)?;
+    if !(put_strike < atm_strike && atm_strike < call_strike) {
+        return Err("Wing strikes do not bracket the ATM strike".into());
+    }
+
+    Ok(FxSmile {
+        forward,
+        t,
+        strikes: vec![put_strike, atm_strike, call_strike],
+        vols: vec![put_vol, atm_vol, call_vol],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const S: f64 = 1.10;
+    const T: f64 = 0.5;
+    const R_D: f64 = 0.05;
+    const R_F: f64 = 0.03;
+    const CONVENTIONS: [DeltaConvention; 4] = [
+        DeltaConvention::Spot,
+        DeltaConvention::Forward,
+        DeltaConvention::PremiumAdjustedSpot,
+        DeltaConvention::PremiumAdjustedForward,
+    ];
+
+    #[test]
+    fn delta_to_strike_round_trips() {
+        for convention in CONVENTIONS {
+            for sigma in [0.08, 0.25] {
+                for option_type in [OptionType::Call, OptionType::Put] {
+                    for delta in [0.1, 0.25, 0.4] {
+                        let quoted = option_type.sign() * delta;
+                        let strike = strike_from_delta(quoted, S, T, R_D, R_F, sigma, option_type, convention).unwrap();
+                        let recovered = fx_delta(S, strike, T, R_D, R_F, sigma, option_type, convention);
+                        assert!((recovered - quoted).abs() < 1e-10, "{convention:?} {option_type:?} {sigma} {quoted}: {recovered}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn premium_adjusted_call_takes_the_upper_branch() {
+        // the same premium-adjusted delta is hit twice; the quote is the strike above the peak
+        for convention in [DeltaConvention::PremiumAdjustedSpot, DeltaConvention::PremiumAdjustedForward] {
+            let strike = strike_from_delta(0.25, S, T, R_D, R_F, 0.25, OptionType::Call, convention).unwrap();
+            let bumped = fx_delta(S, strike * 1.001, T, R_D, R_F, 0.25, OptionType::Call, convention);
+            assert!(bumped < 0.25, "{convention:?}: delta rises with strike at {strike}");
+            assert!(strike_from_delta(0.99, S, T, R_D, R_F, 0.25, OptionType::Call, convention).is_err());
+        }
+    }
+
+    #[test]
+    fn smile_reproduces_its_quotes() {
+        let (atm, rr, bf) = (0.10, -0.015, 0.004);
+        for convention in CONVENTIONS {
+            let smile = fx_smile_from_quotes(S, T, R_D, R_F, atm, rr, bf, 0.25, convention).unwrap();
+            let [put_strike, atm_strike, call_strike] = [smile.strikes[0], smile.strikes[1], smile.strikes[2]];
+            let (put_vol, call_vol) = (smile.vol_at(put_strike), smile.vol_at(call_strike));
+
+            assert!((smile.vol_at(atm_strike) - atm).abs() < 1e-12, "{convention:?}");
+            assert!((call_vol - put_vol - rr).abs() < 1e-12, "{convention:?}");
+            assert!((0.5 * (call_vol + put_vol) - atm - bf).abs() < 1e-12, "{convention:?}");
+
+            // the wings sit at the quoted delta and the ATM straddle is delta neutral
+            let call_delta = fx_delta(S, call_strike, T, R_D, R_F, call_vol, OptionType::Call, convention);
+            let put_delta = fx_delta(S, put_strike, T, R_D, R_F, put_vol, OptionType::Put, convention);
+            assert!((call_delta - 0.25).abs() < 1e-10 && (put_delta + 0.25).abs() < 1e-10, "{convention:?}");
+            let straddle = fx_delta(S, atm_strike, T, R_D, R_F, atm, OptionType::Call, convention)
+                + fx_delta(S, atm_strike, T, R_D, R_F, atm, OptionType::Put, convention);
+            assert!(straddle.abs() < 1e-12, "{convention:?}: {straddle}");
+        }
+    }
+}
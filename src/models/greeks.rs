@@ -0,0 +1,190 @@
+use crate::models::black_scholes::{black_scholes_price, norm_cdf, norm_pdf};
+use crate::models::calendar::TimeBasis;
+use crate::models::dividends::{CashDividend, escrowed_spot, present_value_of_dividends};
+use crate::models::payoff::OptionType;
+
+// Black-Scholes sensitivities with a continuous dividend yield. Volatility
+// enters per 1% vol (so volga is per 1% squared and ultima per 1% cubed),
+// rates per 1%, and theta, charm and color per day of `basis`, which must be
+// the clock `t` was measured on. Dual delta and dual gamma are derivatives
+// with respect to the strike.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Greeks {
+    pub price: f64,
+    pub delta: f64,
+    pub gamma: f64,
+    pub theta: f64,
+    pub vega: f64,
+    pub rho: f64,
+    pub dividend_rho: f64,
+    pub vanna: f64,
+    pub volga: f64,
+    pub charm: f64,
+    pub speed: f64,
+    pub color: f64,
+    pub zomma: f64,
+    pub ultima: f64,
+    pub dual_delta: f64,
+    pub dual_gamma: f64,
+}
+
+fn d1_d2(s: f64, k: f64, t: f64, r: f64, q: f64, sigma: f64) -> (f64, f64) {
+    let d1 = ((s / k).ln() + (r - q + 0.5 * sigma * sigma) * t) / (sigma * t.sqrt());
+    (d1, d1 - sigma * t.sqrt())
+}
+
+pub fn black_scholes_greeks(s: f64, k: f64, t: f64, r: f64, q: f64, sigma: f64, option_type: OptionType, basis: TimeBasis) -> Greeks {
+    let (d1, d2) = d1_d2(s, k, t, r, q, sigma);
+    let phi = option_type.sign();
+    let sqrt_t = t.sqrt();
+    let vol_t = sigma * sqrt_t;
+    let discount_q = (-q * t).exp();
+    let discount_r = (-r * t).exp();
+    let pdf_d1 = norm_pdf(d1);
+    let days_per_year = basis.days_per_year();
+
+    let gamma = discount_q * pdf_d1 / (s * vol_t);
+    let vega = s * discount_q * pdf_d1 * sqrt_t;
+    let theta = -s * discount_q * pdf_d1 * sigma / (2.0 * sqrt_t) - phi * r * k * discount_r * norm_cdf(phi * d2)
+        + phi * q * s * discount_q * norm_cdf(phi * d1);
+    let drift_term = (2.0 * (r - q) * t - d2 * vol_t) / (2.0 * t * vol_t);
+    let charm = phi * q * discount_q * norm_cdf(phi * d1) - discount_q * pdf_d1 * drift_term;
+    let color = discount_q * pdf_d1 / (2.0 * s * t * vol_t) * (2.0 * q * t + 1.0 + 2.0 * t * drift_term * d1);
+
+    Greeks {
+        price: black_scholes_price(s, k, t, r, q, sigma, option_type),
+        delta: phi * discount_q * norm_cdf(phi * d1),
+        gamma,
+        theta: theta / days_per_year,
+        vega: vega / 100.0,
+        rho: phi * k * t * discount_r * norm_cdf(phi * d2) / 100.0,
+        dividend_rho: -phi * s * t * discount_q * norm_cdf(phi * d1) / 100.0,
+        vanna: -discount_q * pdf_d1 * d2 / sigma / 100.0,
+        volga: vega * d1 * d2 / sigma / 1e4,
+        charm: charm / days_per_year,
+        speed: -gamma / s * (d1 / vol_t + 1.0),
+        color: color / days_per_year,
+        zomma: gamma * (d1 * d2 - 1.0) / sigma / 100.0,
+        ultima: -vega / (sigma * sigma) * (d1 * d2 * (1.0 - d1 * d2) + d1 * d1 + d2 * d2) / 1e6,
+        dual_delta: -phi * discount_r * norm_cdf(phi * d2),
+        dual_gamma: discount_r * norm_pdf(d2) / (k * vol_t),
+    }
+}
+
+// Escrowed-dividend Greeks: the suite is evaluated at the spot net of the
+// dividends' present value. That present value accretes at r as time passes
+// and falls as r rises, which feeds back into theta, charm, color and rho
+// through the spot derivatives.
+pub fn black_scholes_greeks_with_dividends(
+    s: f64,
+    k: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    option_type: OptionType,
+    dividends: &[CashDividend],
+    basis: TimeBasis,
+) -> Greeks {
+    let mut greeks = black_scholes_greeks(escrowed_spot(s, t, r, dividends), k, t, r, q, sigma, option_type, basis);
+    let accrual = r * present_value_of_dividends(t, r, dividends) / basis.days_per_year();
+    let duration: f64 = dividends
+        .iter()
+        .filter(|d| d.time > 0.0 && d.time <= t)
+        .map(|d| d.amount * d.time * (-r * d.time).exp())
+        .sum();
+
+    greeks.theta -= greeks.delta * accrual;
+    greeks.charm -= greeks.gamma * accrual;
+    greeks.color -= greeks.speed * accrual;
+    greeks.rho += greeks.delta * duration / 100.0;
+    greeks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const S: f64 = 105.0;
+    const K: f64 = 100.0;
+    const T: f64 = 0.75;
+    const R: f64 = 0.04;
+    const Q: f64 = 0.015;
+    const SIGMA: f64 = 0.25;
+
+    fn greeks(s: f64, k: f64, t: f64, r: f64, q: f64, sigma: f64, option_type: OptionType) -> Greeks {
+        black_scholes_greeks(s, k, t, r, q, sigma, option_type, TimeBasis::Calendar)
+    }
+
+    fn close(analytic: f64, numeric: f64, name: &str) {
+        assert!((analytic - numeric).abs() < 1e-4 * (1.0 + numeric.abs()), "{name}: {analytic} vs {numeric}");
+    }
+
+    #[test]
+    fn matches_finite_differences() {
+        let days = TimeBasis::Calendar.days_per_year();
+        for option_type in [OptionType::Call, OptionType::Put] {
+            let g = greeks(S, K, T, R, Q, SIGMA, option_type);
+            let bump = |ds: f64, dk: f64, dt: f64, dr: f64, dq: f64, dv: f64| {
+                greeks(S + ds, K + dk, T + dt, R + dr, Q + dq, SIGMA + dv, option_type)
+            };
+            let (hs, hv, ht) = (1e-2, 1e-4, 1e-5);
+
+            close(g.delta, (bump(hs, 0.0, 0.0, 0.0, 0.0, 0.0).price - bump(-hs, 0.0, 0.0, 0.0, 0.0, 0.0).price) / (2.0 * hs), "delta");
+            close(g.gamma, (bump(hs, 0.0, 0.0, 0.0, 0.0, 0.0).delta - bump(-hs, 0.0, 0.0, 0.0, 0.0, 0.0).delta) / (2.0 * hs), "gamma");
+            close(g.vega, (bump(0.0, 0.0, 0.0, 0.0, 0.0, hv).price - bump(0.0, 0.0, 0.0, 0.0, 0.0, -hv).price) / (2.0 * hv) / 100.0, "vega");
+            close(g.rho, (bump(0.0, 0.0, 0.0, hv, 0.0, 0.0).price - bump(0.0, 0.0, 0.0, -hv, 0.0, 0.0).price) / (2.0 * hv) / 100.0, "rho");
+            close(g.dividend_rho, (bump(0.0, 0.0, 0.0, 0.0, hv, 0.0).price - bump(0.0, 0.0, 0.0, 0.0, -hv, 0.0).price) / (2.0 * hv) / 100.0, "dividend rho");
+            close(g.theta, -(bump(0.0, 0.0, ht, 0.0, 0.0, 0.0).price - bump(0.0, 0.0, -ht, 0.0, 0.0, 0.0).price) / (2.0 * ht) / days, "theta");
+            close(g.vanna, (bump(0.0, 0.0, 0.0, 0.0, 0.0, hv).delta - bump(0.0, 0.0, 0.0, 0.0, 0.0, -hv).delta) / (2.0 * hv) / 100.0, "vanna");
+            close(g.volga, (bump(0.0, 0.0, 0.0, 0.0, 0.0, hv).vega - bump(0.0, 0.0, 0.0, 0.0, 0.0, -hv).vega) / (2.0 * hv) / 100.0, "volga");
+            close(g.charm, -(bump(0.0, 0.0, ht, 0.0, 0.0, 0.0).delta - bump(0.0, 0.0, -ht, 0.0, 0.0, 0.0).delta) / (2.0 * ht) / days, "charm");
+            close(g.speed, (bump(hs, 0.0, 0.0, 0.0, 0.0, 0.0).gamma - bump(-hs, 0.0, 0.0, 0.0, 0.0, 0.0).gamma) / (2.0 * hs), "speed");
+            close(g.color, -(bump(0.0, 0.0, ht, 0.0, 0.0, 0.0).gamma - bump(0.0, 0.0, -ht, 0.0, 0.0, 0.0).gamma) / (2.0 * ht) / days, "color");
+            close(g.zomma, (bump(0.0, 0.0, 0.0, 0.0, 0.0, hv).gamma - bump(0.0, 0.0, 0.0, 0.0, 0.0, -hv).gamma) / (2.0 * hv) / 100.0, "zomma");
+            close(g.ultima, (bump(0.0, 0.0, 0.0, 0.0, 0.0, hv).volga - bump(0.0, 0.0, 0.0, 0.0, 0.0, -hv).volga) / (2.0 * hv) / 100.0, "ultima");
+            close(g.dual_delta, (bump(0.0, hs, 0.0, 0.0, 0.0, 0.0).price - bump(0.0, -hs, 0.0, 0.0, 0.0, 0.0).price) / (2.0 * hs), "dual delta");
+            close(g.dual_gamma, (bump(0.0, hs, 0.0, 0.0, 0.0, 0.0).dual_delta - bump(0.0, -hs, 0.0, 0.0, 0.0, 0.0).dual_delta) / (2.0 * hs), "dual gamma");
+        }
+    }
+
+    #[test]
+    fn put_call_parity() {
+        let call = greeks(S, K, T, R, Q, SIGMA, OptionType::Call);
+        let put = greeks(S, K, T, R, Q, SIGMA, OptionType::Put);
+        close(call.delta - put.delta, (-Q * T).exp(), "delta parity");
+        close(call.gamma, put.gamma, "gamma parity");
+        close(call.vega, put.vega, "vega parity");
+    }
+
+    #[test]
+    fn trading_basis_scales_per_day_greeks() {
+        let calendar = greeks(S, K, T, R, Q, SIGMA, OptionType::Call);
+        let trading = black_scholes_greeks(S, K, T, R, Q, SIGMA, OptionType::Call, TimeBasis::Trading);
+        let ratio = TimeBasis::Calendar.days_per_year() / TimeBasis::Trading.days_per_year();
+        close(trading.theta, calendar.theta * ratio, "theta");
+        close(trading.charm, calendar.charm * ratio, "charm");
+        close(trading.color, calendar.color * ratio, "color");
+        close(trading.vega, calendar.vega, "vega");
+    }
+
+    #[test]
+    fn escrowed_greeks_match_finite_differences() {
+        let dividends = [CashDividend::new(0.2, 1.5), CashDividend::new(0.45, 1.5), CashDividend::new(0.9, 1.5)];
+        let days = TimeBasis::Calendar.days_per_year();
+        // moving the clock forward shortens the expiry and every ex-date together
+        let shifted = |h: f64| dividends.map(|d| CashDividend::new(d.time - h, d.amount));
+        let value = |s: f64, t: f64, r: f64, divs: &[CashDividend]| {
+            black_scholes_greeks_with_dividends(s, K, t, r, Q, SIGMA, OptionType::Call, divs, TimeBasis::Calendar)
+        };
+        let g = value(S, T, R, &dividends);
+        let (hs, hr, ht) = (1e-2, 1e-4, 1e-5);
+
+        close(g.price, black_scholes_greeks(S - present_value_of_dividends(T, R, &dividends), K, T, R, Q, SIGMA, OptionType::Call, TimeBasis::Calendar).price, "price");
+        close(g.delta, (value(S + hs, T, R, &dividends).price - value(S - hs, T, R, &dividends).price) / (2.0 * hs), "delta");
+        close(g.rho, (value(S, T, R + hr, &dividends).price - value(S, T, R - hr, &dividends).price) / (2.0 * hr) / 100.0, "rho");
+        close(g.theta, (value(S, T - ht, R, &shifted(ht)).price - value(S, T + ht, R, &shifted(-ht)).price) / (2.0 * ht) / days, "theta");
+        close(g.charm, (value(S, T - ht, R, &shifted(ht)).delta - value(S, T + ht, R, &shifted(-ht)).delta) / (2.0 * ht) / days, "charm");
+        close(g.color, (value(S, T - ht, R, &shifted(ht)).gamma - value(S, T + ht, R, &shifted(-ht)).gamma) / (2.0 * ht) / days, "color");
+    }
+}
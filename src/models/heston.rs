@@ -0,0 +1,180 @@
+use nalgebra::Complex;
+use statrs::distribution::{Normal, ContinuousCDF};
+use rand::prelude::*;
+use std::f64::consts::PI;
+use crate::models::fourier::CharacteristicFunction;
+use crate::models::monte_carlo::{price_from_paths, simulate_paths_with};
+use crate::models::payoff::Payoff;
+use crate::models::quadrature::adaptive_simpson;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HestonParams {
+    pub v0: f64,
+    pub kappa: f64,
+    pub theta: f64,
+    pub xi: f64,
+    pub rho: f64,
+}
+
+impl HestonParams {
+    pub fn new(v0: f64, kappa: f64, theta: f64, xi: f64, rho: f64) -> Self {
+        HestonParams { v0, kappa, theta, xi, rho }
+    }
+
+    pub fn feller_ratio(&self) -> f64 {
+        2.0 * self.kappa * self.theta / (self.xi * self.xi)
+    }
+
+    pub fn feller_satisfied(&self) -> bool {
+        self.feller_ratio() >= 1.0
+    }
+}
+
+// Characteristic function of ln(S_T), using the Albrecher et al. "little trap"
+// form which avoids the branch-cut discontinuity of the original formulation.
+pub fn heston_characteristic_function(
+    u: Complex<f64>,
+    s: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    params: &HestonParams,
+) -> Complex<f64> {
+    let i = Complex::new(0.0, 1.0);
+    let HestonParams { v0, kappa, theta, xi, rho } = *params;
+
+    let beta = kappa - rho * xi * i * u;
+    let d = (beta * beta + xi * xi * (i * u + u * u)).sqrt();
+    let g = (beta - d) / (beta + d);
+    let exp_dt = (-d * t).exp();
+
+    let c = (r - q) * i * u * t
+        + kappa * theta / (xi * xi) * ((beta - d) * t - 2.0 * ((1.0 - g * exp_dt) / (1.0 - g)).ln());
+    let big_d = (beta - d) / (xi * xi) * (1.0 - exp_dt) / (1.0 - g * exp_dt);
+
+    (c + big_d * v0 + i * u * s.ln()).exp()
+}
+
+impl CharacteristicFunction for HestonParams {
+    fn characteristic_function(&self, u: Complex<f64>, t: f64) -> Complex<f64> {
+        heston_characteristic_function(u, 1.0, t, 0.0, 0.0, self)
+    }
+}
+
+fn integration_limit(t: f64, params: &HestonParams) -> f64 {
+    let variance = params.v0.min(params.theta).max(1e-4);
+    (12.0 / (variance * t).sqrt()).max(200.0)
+}
+
+pub fn heston_call(s: f64, k: f64, t: f64, r: f64, q: f64, params: &HestonParams) -> f64 {
+    let i = Complex::new(0.0, 1.0);
+    let ln_k = k.ln();
+    let forward = s * ((r - q) * t).exp();
+
+    let p1_integrand = |u: f64| {
+        let phi = heston_characteristic_function(Complex::new(u, -1.0), s, t, r, q, params);
+        ((-i * u * ln_k).exp() * phi / (i * u * forward)).re
+    };
+    let p2_integrand = |u: f64| {
+        let phi = heston_characteristic_function(Complex::new(u, 0.0), s, t, r, q, params);
+        ((-i * u * ln_k).exp() * phi / (i * u)).re
+    };
+
+    let upper = integration_limit(t, params);
+    let p1 = 0.5 + adaptive_simpson(p1_integrand, 1e-8, upper, 1e-10).value / PI;
+    let p2 = 0.5 + adaptive_simpson(p2_integrand, 1e-8, upper, 1e-10).value / PI;
+
+    (s * (-q * t).exp() * p1 - k * (-r * t).exp() * p2).max(0.0)
+}
+
+pub fn heston_put(s: f64, k: f64, t: f64, r: f64, q: f64, params: &HestonParams) -> f64 {
+    let call = heston_call(s, k, t, r, q, params);
+    (call - s * (-q * t).exp() + k * (-r * t).exp()).max(0.0)
+}
+
+// Full-truncation Euler: the variance process may go negative between steps,
+// but only its positive part ever enters the drift and diffusion terms.
+pub fn simulate_heston_paths(
+    s0: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    params: &HestonParams,
+    num_steps: usize,
+    num_paths: usize,
+) -> Vec<Vec<f64>> {
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let dt = t / num_steps as f64;
+    let sqrt_dt = dt.sqrt();
+    let rho_bar = (1.0 - params.rho * params.rho).sqrt();
+
+    simulate_paths_with(s0, (s0.ln(), params.v0), num_steps, num_paths, |rng, (ln_s, v), _| {
+        let z1 = normal.inverse_cdf(rng.random::<f64>());
+        let z2 = params.rho * z1 + rho_bar * normal.inverse_cdf(rng.random::<f64>());
+        let v_pos = v.max(0.0);
+        *ln_s += (r - q - 0.5 * v_pos) * dt + v_pos.sqrt() * sqrt_dt * z1;
+        *v += params.kappa * (params.theta - v_pos) * dt + params.xi * v_pos.sqrt() * sqrt_dt * z2;
+        ln_s.exp()
+    })
+}
+
+pub fn heston_monte_carlo_price<P: Payoff>(
+    s0: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    params: &HestonParams,
+    payoff: &P,
+    num_steps: usize,
+    num_paths: usize,
+) -> f64 {
+    let paths = simulate_heston_paths(s0, t, r, q, params, num_steps, num_paths);
+    price_from_paths(&paths, t, r, payoff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::black_scholes::black_scholes_price;
+    use crate::models::fourier::{fourier_price_strikes, FourierMethod};
+    use crate::models::payoff::{OptionType, Vanilla};
+
+    const S: f64 = 100.0;
+    const T: f64 = 1.0;
+    const R: f64 = 0.03;
+    const Q: f64 = 0.01;
+
+    fn params() -> HestonParams {
+        HestonParams::new(0.04, 1.5, 0.05, 0.5, -0.7)
+    }
+
+    #[test]
+    fn quadrature_agrees_with_cos_and_carr_madan() {
+        let strikes = [70.0, 85.0, 100.0, 115.0, 130.0];
+        let cos = fourier_price_strikes(&params(), S, &strikes, T, R, Q, OptionType::Call, FourierMethod::Cos);
+        let carr_madan = fourier_price_strikes(&params(), S, &strikes, T, R, Q, OptionType::Call, FourierMethod::CarrMadan);
+        for (i, &k) in strikes.iter().enumerate() {
+            let quadrature = heston_call(S, k, T, R, Q, &params());
+            assert!((quadrature - cos[i]).abs() < 1e-4, "K={k}: {quadrature} vs COS {}", cos[i]);
+            assert!((quadrature - carr_madan[i]).abs() < 1e-2, "K={k}: {quadrature} vs Carr-Madan {}", carr_madan[i]);
+        }
+    }
+
+    #[test]
+    fn collapses_to_black_scholes_without_vol_of_vol() {
+        let flat = HestonParams::new(0.04, 2.0, 0.04, 1e-3, 0.0);
+        for k in [80.0, 100.0, 120.0] {
+            let heston = heston_call(S, k, T, R, Q, &flat);
+            let black_scholes = black_scholes_price(S, k, T, R, Q, 0.2, OptionType::Call);
+            assert!((heston - black_scholes).abs() < 1e-4, "K={k}: {heston} vs {black_scholes}");
+        }
+    }
+
+    #[test]
+    fn monte_carlo_agrees_within_discretisation_error() {
+        let k = 100.0;
+        let mc = heston_monte_carlo_price(S, T, R, Q, &params(), &Vanilla::new(OptionType::Put, k), 50, 40_000);
+        let exact = heston_put(S, k, T, R, Q, &params());
+        assert!((mc - exact).abs() < 0.25, "{mc} vs {exact}");
+    }
+}
@@ -0,0 +1,172 @@
+use nalgebra::DVector;
+use crate::models::black_scholes::{black_scholes_price, black_scholes_vega};
+use crate::models::calibration::CalibrationQuote;
+use crate::models::heston::{heston_call, heston_put, HestonParams};
+use crate::models::implied_vol::implied_volatility;
+use crate::models::optimize::levenberg_marquardt;
+use crate::models::payoff::OptionType;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HestonBounds {
+    pub lower: [f64; 5],
+    pub upper: [f64; 5],
+}
+
+impl Default for HestonBounds {
+    fn default() -> Self {
+        HestonBounds {
+            lower: [1e-4, 1e-3, 1e-4, 1e-3, -0.999],
+            upper: [4.0, 20.0, 4.0, 5.0, 0.999],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HestonCalibration {
+    pub params: HestonParams,
+    pub feller_ratio: f64,
+    pub feller_satisfied: bool,
+    pub rmse_iv: f64,
+    pub max_abs_iv_error: f64,
+    pub iv_errors: Vec<f64>,
+    pub iterations: usize,
+    pub converged: bool,
+}
+
+fn to_vector(params: &HestonParams) -> DVector<f64> {
+    DVector::from_vec(vec![params.v0, params.kappa, params.theta, params.xi, params.rho])
+}
+
+fn from_vector(p: &DVector<f64>) -> HestonParams {
+    HestonParams::new(p[0], p[1], p[2], p[3], p[4])
+}
+
+fn heston_price(s: f64, q: f64, quote: &CalibrationQuote, params: &HestonParams) -> f64 {
+    match quote.out_of_the_money_type(s, q) {
+        OptionType::Call => heston_call(s, quote.strike, quote.t, quote.r, q, params),
+        OptionType::Put => heston_put(s, quote.strike, quote.t, quote.r, q, params),
+    }
+}
+
+// Price errors divided by Black-Scholes vega, i.e. first-order implied vol errors.
+fn residuals(
+    p: &DVector<f64>,
+    quotes: &[CalibrationQuote],
+    market_prices: &[f64],
+    vegas: &[f64],
+    s: f64,
+    q: f64,
+) -> DVector<f64> {
+    let params = from_vector(p);
+    DVector::from_iterator(
+        quotes.len(),
+        quotes.iter().enumerate().map(|(i, quote)| {
+            (heston_price(s, q, quote, &params) - market_prices[i]) / vegas[i]
+        }),
+    )
+}
+
+// Levenberg-Marquardt on the vega-weighted price errors, with the parameters
+// kept inside `bounds`.
+pub fn calibrate_heston(
+    s: f64,
+    q: f64,
+    quotes: &[CalibrationQuote],
+    initial: &HestonParams,
+    bounds: &HestonBounds,
+    max_iterations: usize,
+) -> Result<HestonCalibration, Box<dyn std::error::Error>> {
+    if quotes.len() < 5 {
+        return Err("Need at least 5 quotes to calibrate the Heston model".into());
+    }
+
+    let market_prices: Vec<f64> = quotes
+        .iter()
+        .map(|quote| black_scholes_price(s, quote.strike, quote.t, quote.r, q, quote.implied_vol, quote.out_of_the_money_type(s, q)))
+        .collect();
+    let vegas: Vec<f64> = quotes
+        .iter()
+        .map(|quote| black_scholes_vega(s, quote.strike, quote.t, quote.r, q, quote.implied_vol).max(1e-4))
+        .collect();
+
+    let fit = levenberg_marquardt(
+        |p| residuals(p, quotes, &market_prices, &vegas, s, q),
+        to_vector(initial),
+        &bounds.lower,
+        &bounds.upper,
+        max_iterations,
+    );
+
+    let params = from_vector(&fit.params);
+    let iv_errors: Vec<f64> = quotes
+        .iter()
+        .zip(fit.residuals.iter())
+        .map(|(quote, &approx)| {
+            let model_price = heston_price(s, q, quote, &params);
+            implied_volatility(model_price, s, quote.strike, quote.t, quote.r, q, quote.out_of_the_money_type(s, q))
+                .map(|iv| iv - quote.implied_vol)
+                .unwrap_or(approx)
+        })
+        .collect();
+    let rmse_iv = (iv_errors.iter().map(|e| e * e).sum::<f64>() / iv_errors.len() as f64).sqrt();
+    let max_abs_iv_error = iv_errors.iter().fold(0.0_f64, |acc, e| acc.max(e.abs()));
+
+    Ok(HestonCalibration {
+        params,
+        feller_ratio: params.feller_ratio(),
+        feller_satisfied: params.feller_satisfied(),
+        rmse_iv,
+        max_abs_iv_error,
+        iv_errors,
+        iterations: fit.iterations,
+        converged: fit.converged,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const S: f64 = 100.0;
+    const Q: f64 = 0.01;
+
+    // Implied vols of `truth` on a grid of strikes either side of the forward,
+    // so both the put and the call branch are exercised.
+    fn synthetic_quotes(truth: &HestonParams) -> Vec<CalibrationQuote> {
+        let mut quotes = Vec::new();
+        for &(t, r) in &[(0.25, 0.03), (0.5, 0.035), (1.0, 0.04), (2.0, 0.045)] {
+            for &strike in &[80.0, 90.0, 95.0, 100.0, 105.0, 110.0, 120.0] {
+                let quote = CalibrationQuote { strike, t, r, implied_vol: 0.0 };
+                let price = heston_price(S, Q, &quote, truth);
+                let implied_vol = implied_volatility(price, S, strike, t, r, Q, quote.out_of_the_money_type(S, Q)).unwrap();
+                quotes.push(CalibrationQuote { implied_vol, ..quote });
+            }
+        }
+        quotes
+    }
+
+    #[test]
+    fn recovers_known_parameters() {
+        // one set inside the Feller condition and one outside it
+        for truth in [HestonParams::new(0.05, 2.0, 0.04, 0.3, -0.6), HestonParams::new(0.03, 1.0, 0.06, 0.6, -0.4)] {
+            let quotes = synthetic_quotes(&truth);
+            let initial = HestonParams::new(0.04, 1.5, 0.05, 0.5, -0.3);
+            let fit = calibrate_heston(S, Q, &quotes, &initial, &HestonBounds::default(), 200).unwrap();
+            let p = fit.params;
+            assert!(fit.rmse_iv < 1e-4, "{truth:?}: rmse {}", fit.rmse_iv);
+            assert!((p.v0 - truth.v0).abs() < 1e-3, "{p:?} vs {truth:?}");
+            assert!((p.theta - truth.theta).abs() < 2e-3, "{p:?} vs {truth:?}");
+            assert!((p.kappa - truth.kappa).abs() < 0.1 * truth.kappa, "{p:?} vs {truth:?}");
+            assert!((p.xi - truth.xi).abs() < 0.02, "{p:?} vs {truth:?}");
+            assert!((p.rho - truth.rho).abs() < 0.02, "{p:?} vs {truth:?}");
+            assert_eq!(fit.feller_satisfied, truth.feller_satisfied());
+            assert!((fit.feller_ratio - truth.feller_ratio()).abs() < 0.1 * truth.feller_ratio());
+        }
+    }
+
+    #[test]
+    fn too_few_quotes_is_an_error() {
+        let quotes = synthetic_quotes(&HestonParams::new(0.04, 2.0, 0.04, 0.3, -0.6));
+        assert!(calibrate_heston(S, Q, &quotes[..4], &HestonParams::new(0.04, 2.0, 0.04, 0.3, -0.6), &HestonBounds::default(), 10).is_err());
+    }
+}
@@ -0,0 +1,192 @@
+use std::fmt;
+use crate::models::black_scholes::{black_scholes_call, black_scholes_put, black_scholes_vega};
+use crate::models::payoff::OptionType;
+
+const PRICE_TOLERANCE: f64 = 1e-10;
+const MAX_ITERATIONS: usize = 100;
+const MIN_VOL: f64 = 1e-8;
+const MAX_VOL: f64 = 20.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImpliedVolError {
+    InvalidInput,
+    Expired,
+    BelowIntrinsic { price: f64, lower_bound: f64 },
+    AboveUpperBound { price: f64, upper_bound: f64 },
+    NoConvergence { iterations: usize, last_vol: f64 },
+}
+
+impl fmt::Display for ImpliedVolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImpliedVolError::InvalidInput => write!(f, "spot, strike and price must be positive and finite"),
+            ImpliedVolError::Expired => write!(f, "option has no time to expiry"),
+            ImpliedVolError::BelowIntrinsic { price, lower_bound } => {
+                write!(f, "price {:.6} is at or below the no-arbitrage lower bound {:.6}", price, lower_bound)
+            }
+            ImpliedVolError::AboveUpperBound { price, upper_bound } => {
+                write!(f, "price {:.6} is at or above the no-arbitrage upper bound {:.6}", price, upper_bound)
+            }
+            ImpliedVolError::NoConvergence { iterations, last_vol } => {
+                write!(f, "no convergence after {} iterations (last vol {:.6})", iterations, last_vol)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ImpliedVolError {}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuoteImpliedVols {
+    pub bid: Option<f64>,
+    pub mid: f64,
+    pub ask: Option<f64>,
+}
+
+fn price_for(s: f64, k: f64, t: f64, r: f64, q: f64, sigma: f64, is_call: bool) -> f64 {
+    if is_call {
+        black_scholes_call(s, k, t, r, q, sigma)
+    } else {
+        black_scholes_put(s, k, t, r, q, sigma)
+    }
+}
+
+// Inverts Black-Scholes for sigma. In-the-money quotes are mapped onto the
+// out-of-the-money option through put-call parity, where the price carries far
+// more volatility information, and then solved with a Newton iteration that
+// falls back to bisection whenever a step leaves the bracket or vega vanishes.
+pub fn implied_volatility(
+    price: f64,
+    s: f64,
+    k: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    option_type: OptionType,
+) -> Result<f64, ImpliedVolError> {
+    let is_call = option_type == OptionType::Call;
+    if !(price.is_finite() && s.is_finite() && k.is_finite()) || price <= 0.0 || s <= 0.0 || k <= 0.0 {
+        return Err(ImpliedVolError::InvalidInput);
+    }
+    if t <= 0.0 || !t.is_finite() {
+        return Err(ImpliedVolError::Expired);
+    }
+
+    let forward_spot = s * (-q * t).exp();
+    let discounted_strike = k * (-r * t).exp();
+    let (lower_bound, upper_bound) = if is_call {
+        ((forward_spot - discounted_strike).max(0.0), forward_spot)
+    } else {
+        ((discounted_strike - forward_spot).max(0.0), discounted_strike)
+    };
+    if price <= lower_bound + PRICE_TOLERANCE {
+        return Err(ImpliedVolError::BelowIntrinsic { price, lower_bound });
+    }
+    if price >= upper_bound - PRICE_TOLERANCE {
+        return Err(ImpliedVolError::AboveUpperBound { price, upper_bound });
+    }
+
+    let call_in_the_money = forward_spot > discounted_strike;
+    let (target, solve_call) = match (is_call, call_in_the_money) {
+        (true, true) => (price - forward_spot + discounted_strike, false),
+        (false, false) => (price + forward_spot - discounted_strike, true),
+        _ => (price, is_call),
+    };
+
+    let mut lo = MIN_VOL;
+    let mut hi = MAX_VOL;
+    let moneyness = ((s / k).ln() + (r - q) * t).abs();
+    let mut sigma = (2.0 * moneyness / t).sqrt().max(0.2).min(hi);
+
+    for _ in 0..MAX_ITERATIONS {
+        let diff = price_for(s, k, t, r, q, sigma, solve_call) - target;
+        if diff.abs() < PRICE_TOLERANCE {
+            return Ok(sigma);
+        }
+        if diff > 0.0 {
+            hi = sigma;
+        } else {
+            lo = sigma;
+        }
+
+        let v = black_scholes_vega(s, k, t, r, q, sigma);
+        let newton = sigma - diff / v;
+        sigma = if v > 1e-12 && newton > lo && newton < hi {
+            newton
+        } else {
+            0.5 * (lo + hi)
+        };
+
+        if hi - lo < 1e-14 {
+            return Ok(sigma);
+        }
+    }
+
+    Err(ImpliedVolError::NoConvergence { iterations: MAX_ITERATIONS, last_vol: sigma })
+}
+
+pub fn implied_volatility_from_quotes(
+    bid: f64,
+    ask: f64,
+    s: f64,
+    k: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    option_type: OptionType,
+) -> Result<QuoteImpliedVols, ImpliedVolError> {
+    if bid < 0.0 || ask <= 0.0 || ask < bid {
+        return Err(ImpliedVolError::InvalidInput);
+    }
+    let mid = implied_volatility(0.5 * (bid + ask), s, k, t, r, q, option_type)?;
+    Ok(QuoteImpliedVols {
+        bid: implied_volatility(bid, s, k, t, r, q, option_type).ok(),
+        mid,
+        ask: implied_volatility(ask, s, k, t, r, q, option_type).ok(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::black_scholes::black_scholes_price;
+
+    #[test]
+    fn round_trips_across_moneyness_and_expiry() {
+        let (s, r, q) = (100.0, 0.04, 0.015);
+        for option_type in [OptionType::Call, OptionType::Put] {
+            for k in [50.0, 80.0, 100.0, 125.0, 200.0] {
+                for t in [0.02, 0.5, 3.0] {
+                    for sigma in [0.05, 0.2, 0.8, 2.5] {
+                        // skip deep wings whose time value is lost to rounding
+                        if black_scholes_vega(s, k, t, r, q, sigma) < 1e-4 {
+                            continue;
+                        }
+                        let price = black_scholes_price(s, k, t, r, q, sigma, option_type);
+                        let solved = implied_volatility(price, s, k, t, r, q, option_type).unwrap();
+                        assert!((solved - sigma).abs() < 1e-6, "{option_type:?} K={k} T={t}: {solved} vs {sigma}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_prices_outside_the_no_arbitrage_bounds() {
+        let (s, k, t, r, q): (f64, f64, f64, f64, f64) = (100.0, 90.0, 1.0, 0.03, 0.0);
+        let intrinsic = s - k * (-r * t).exp();
+        assert!(matches!(implied_volatility(intrinsic - 0.01, s, k, t, r, q, OptionType::Call), Err(ImpliedVolError::BelowIntrinsic { .. })));
+        assert!(matches!(implied_volatility(s + 1.0, s, k, t, r, q, OptionType::Call), Err(ImpliedVolError::AboveUpperBound { .. })));
+        assert!(matches!(implied_volatility(5.0, s, k, 0.0, r, q, OptionType::Call), Err(ImpliedVolError::Expired)));
+        assert!(matches!(implied_volatility(-1.0, s, k, t, r, q, OptionType::Call), Err(ImpliedVolError::InvalidInput)));
+    }
+
+    #[test]
+    fn quote_vols_bracket_the_mid() {
+        let (s, k, t, r, q) = (100.0, 105.0, 0.5, 0.03, 0.01);
+        let mid = black_scholes_price(s, k, t, r, q, 0.3, OptionType::Put);
+        let vols = implied_volatility_from_quotes(mid - 0.1, mid + 0.1, s, k, t, r, q, OptionType::Put).unwrap();
+        assert!((vols.mid - 0.3).abs() < 1e-8);
+        assert!(vols.bid.unwrap() < vols.mid && vols.mid < vols.ask.unwrap());
+    }
+}
@@ -0,0 +1,128 @@
+use statrs::distribution::{Normal, ContinuousCDF};
+use rand::prelude::*;
+use crate::models::monte_carlo::price_from_paths;
+use crate::models::payoff::Payoff;
+use crate::models::vol_surface::VolatilitySurface;
+
+const MIN_LOCAL_VARIANCE: f64 = 1e-6;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalVolGrid {
+    pub times: Vec<f64>,
+    pub spots: Vec<f64>,
+    pub vols: Vec<Vec<f64>>,
+}
+
+// Dupire's formula written in terms of total implied variance w(y, T) at
+// log-moneyness y = ln(K / F(T)) (Gatheral, "The Volatility Surface", eq. 1.10).
+pub fn dupire_local_vol<S: VolatilitySurface>(surface: &S, strike: f64, t: f64) -> f64 {
+    let dt = 1e-4_f64.min(0.5 * t);
+    let dy = 1e-3;
+    let y = (strike / surface.forward(t)).ln();
+
+    let w = surface.total_variance(y, t);
+    let dw_dt = (surface.total_variance(y, t + dt) - surface.total_variance(y, t - dt)) / (2.0 * dt);
+    let w_up = surface.total_variance(y + dy, t);
+    let w_down = surface.total_variance(y - dy, t);
+    let dw_dy = (w_up - w_down) / (2.0 * dy);
+    let d2w_dy2 = (w_up - 2.0 * w + w_down) / (dy * dy);
+
+    let denominator = 1.0 - y / w * dw_dy
+        + 0.25 * (-0.25 - 1.0 / w + y * y / (w * w)) * dw_dy * dw_dy
+        + 0.5 * d2w_dy2;
+
+    (dw_dt / denominator).max(MIN_LOCAL_VARIANCE).sqrt()
+}
+
+fn bracket(grid: &[f64], x: f64) -> (usize, f64) {
+    if x <= grid[0] {
+        return (0, 0.0);
+    }
+    let last = grid.len() - 1;
+    if x >= grid[last] {
+        return (last - 1, 1.0);
+    }
+    let i = grid.iter().position(|&g| g > x).unwrap() - 1;
+    (i, (x - grid[i]) / (grid[i + 1] - grid[i]))
+}
+
+impl LocalVolGrid {
+    pub fn from_surface<S: VolatilitySurface>(
+        surface: &S,
+        max_t: f64,
+        num_times: usize,
+        spot_min: f64,
+        spot_max: f64,
+        num_spots: usize,
+    ) -> Self {
+        let num_times = num_times.max(2);
+        let num_spots = num_spots.max(2);
+        let times: Vec<f64> = (1..=num_times).map(|i| max_t * i as f64 / num_times as f64).collect();
+        let spots: Vec<f64> = (0..num_spots)
+            .map(|j| spot_min + (spot_max - spot_min) * j as f64 / (num_spots - 1) as f64)
+            .collect();
+        let vols = times
+            .iter()
+            .map(|&t| spots.iter().map(|&s| dupire_local_vol(surface, s, t)).collect())
+            .collect();
+
+        LocalVolGrid { times, spots, vols }
+    }
+
+    // Bilinear interpolation, flat outside the grid.
+    pub fn local_vol(&self, s: f64, t: f64) -> f64 {
+        let (i, wt) = bracket(&self.times, t);
+        let (j, ws) = bracket(&self.spots, s);
+        let v00 = self.vols[i][j];
+        let v01 = self.vols[i][j + 1];
+        let v10 = self.vols[i + 1][j];
+        let v11 = self.vols[i + 1][j + 1];
+        (1.0 - wt) * ((1.0 - ws) * v00 + ws * v01) + wt * ((1.0 - ws) * v10 + ws * v11)
+    }
+}
+
+pub fn simulate_local_vol_paths(
+    s0: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    grid: &LocalVolGrid,
+    num_steps: usize,
+    num_paths: usize,
+) -> Vec<Vec<f64>> {
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let mut rng = rand::rng();
+    let dt = t / num_steps as f64;
+    let mut paths = Vec::with_capacity(num_paths);
+
+    for _ in 0..num_paths {
+        let mut path = Vec::with_capacity(num_steps + 1);
+        path.push(s0);
+        let mut current_price = s0;
+
+        for step in 0..num_steps {
+            let sigma = grid.local_vol(current_price, step as f64 * dt);
+            let z = normal.inverse_cdf(rng.random::<f64>());
+            current_price *= ((r - q - 0.5 * sigma * sigma) * dt + sigma * z * dt.sqrt()).exp();
+            path.push(current_price);
+        }
+
+        paths.push(path);
+    }
+
+    paths
+}
+
+pub fn local_vol_monte_carlo_price<P: Payoff>(
+    s0: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    grid: &LocalVolGrid,
+    payoff: &P,
+    num_steps: usize,
+    num_paths: usize,
+) -> f64 {
+    let paths = simulate_local_vol_paths(s0, t, r, q, grid, num_steps, num_paths);
+    price_from_paths(&paths, t, r, payoff)
+}
@@ -0,0 +1,178 @@
+use nalgebra::{DMatrix, DVector};
+use crate::models::monte_carlo::simulate_gbm_paths;
+use crate::models::payoff::{ExerciseStyle, Payoff};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LsmResult {
+    pub price: f64,
+    pub lower_bound: f64,
+    pub std_error: f64,
+}
+
+const BASIS_SIZE: usize = 4;
+
+fn basis(spot: f64, k: f64) -> [f64; BASIS_SIZE] {
+    let x = spot / k;
+    [1.0, x, x * x, x * x * x]
+}
+
+fn regress(xs: &[[f64; BASIS_SIZE]], ys: &[f64]) -> Option<DVector<f64>> {
+    let n = xs.len();
+    if n <= BASIS_SIZE {
+        return None;
+    }
+    let mut a_mat = DMatrix::zeros(n, BASIS_SIZE);
+    for (i, row) in xs.iter().enumerate() {
+        for (j, &value) in row.iter().enumerate() {
+            a_mat[(i, j)] = value;
+        }
+    }
+    let b_vec = DVector::from_iterator(n, ys.iter().cloned());
+    let lhs = a_mat.transpose() * &a_mat;
+    let rhs = a_mat.transpose() * &b_vec;
+    lhs.lu().solve(&rhs)
+}
+
+fn continuation_value(coeffs: &DVector<f64>, spot: f64, k: f64) -> f64 {
+    basis(spot, k).iter().zip(coeffs.iter()).map(|(b, c)| b * c).sum()
+}
+
+// Prices with the Longstaff-Schwartz algorithm. The regression is fitted on one
+// set of paths (`price` is the in-sample estimate) and the resulting exercise
+// policy is applied to an independent set, which gives an unbiased lower bound.
+pub fn longstaff_schwartz_price<P: Payoff>(
+    s0: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    payoff: &P,
+    exercise: &ExerciseStyle,
+    num_steps: usize,
+    num_paths: usize,
+) -> LsmResult {
+    let dt = t / num_steps as f64;
+    let k = payoff.strike();
+    let exercisable = exercise.exercise_steps(t, num_steps);
+    let payoff = |spot: f64| payoff.payoff(spot);
+
+    let training = simulate_gbm_paths(s0, t, r, q, sigma, num_steps, num_paths);
+    let mut cashflows: Vec<f64> = training.iter().map(|path| payoff(path[num_steps])).collect();
+    let mut cashflow_steps = vec![num_steps; num_paths];
+    let mut policy: Vec<Option<DVector<f64>>> = vec![None; num_steps + 1];
+
+    for step in (1..num_steps).rev() {
+        if !exercisable[step] {
+            continue;
+        }
+        let itm: Vec<usize> = (0..num_paths).filter(|&i| payoff(training[i][step]) > 0.0).collect();
+        let xs: Vec<[f64; BASIS_SIZE]> = itm.iter().map(|&i| basis(training[i][step], k)).collect();
+        let ys: Vec<f64> = itm
+            .iter()
+            .map(|&i| cashflows[i] * (-r * (cashflow_steps[i] - step) as f64 * dt).exp())
+            .collect();
+
+        if let Some(coeffs) = regress(&xs, &ys) {
+            for &i in &itm {
+                let exercise = payoff(training[i][step]);
+                if exercise > continuation_value(&coeffs, training[i][step], k) {
+                    cashflows[i] = exercise;
+                    cashflow_steps[i] = step;
+                }
+            }
+            policy[step] = Some(coeffs);
+        }
+    }
+
+    let in_sample = cashflows
+        .iter()
+        .zip(cashflow_steps.iter())
+        .map(|(cf, &step)| cf * (-r * step as f64 * dt).exp())
+        .sum::<f64>()
+        / num_paths as f64;
+
+    let pricing = simulate_gbm_paths(s0, t, r, q, sigma, num_steps, num_paths);
+    let discounted: Vec<f64> = pricing
+        .iter()
+        .map(|path| {
+            for step in 1..num_steps {
+                if let Some(coeffs) = &policy[step] {
+                    let exercise = payoff(path[step]);
+                    if exercise > 0.0 && exercise > continuation_value(coeffs, path[step], k) {
+                        return exercise * (-r * step as f64 * dt).exp();
+                    }
+                }
+            }
+            payoff(path[num_steps]) * (-r * t).exp()
+        })
+        .collect();
+
+    let n = discounted.len() as f64;
+    let mean = discounted.iter().sum::<f64>() / n;
+    let variance = discounted.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0);
+
+    let immediate = if exercisable[0] { payoff(s0) } else { 0.0 };
+
+    LsmResult {
+        price: in_sample.max(immediate),
+        lower_bound: mean.max(immediate),
+        std_error: (variance / n).sqrt(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::binomial::{TreeMethod, binomial_option_price};
+    use crate::models::black_scholes::black_scholes_put;
+    use crate::models::calendar::TimeBasis;
+    use crate::models::payoff::{OptionType, Vanilla};
+
+    // The Longstaff-Schwartz (2001) test case: a deep in-the-money put with
+    // a material early-exercise premium
+    const S: f64 = 36.0;
+    const K: f64 = 40.0;
+    const T: f64 = 1.0;
+    const R: f64 = 0.06;
+    const SIGMA: f64 = 0.2;
+    const STEPS: usize = 50;
+
+    fn tree(exercise: &ExerciseStyle) -> f64 {
+        let put = Vanilla::new(OptionType::Put, K);
+        binomial_option_price(S, T, R, 0.0, SIGMA, &put, exercise, 1000, TreeMethod::LeisenReimer, TimeBasis::Calendar).price
+    }
+
+    #[test]
+    fn american_put_matches_the_tree() {
+        let put = Vanilla::new(OptionType::Put, K);
+        let lsm = longstaff_schwartz_price(S, T, R, 0.0, SIGMA, &put, &ExerciseStyle::American, STEPS, 20_000);
+        let american = tree(&ExerciseStyle::American);
+        // the lower bound is biased low by the suboptimal policy and by only
+        // exercising on the simulation grid
+        assert!(lsm.lower_bound < american + 3.0 * lsm.std_error, "{} +/- {} vs {american}", lsm.lower_bound, lsm.std_error);
+        assert!(lsm.lower_bound > american - 3.0 * lsm.std_error - 0.03, "{} +/- {} vs {american}", lsm.lower_bound, lsm.std_error);
+        assert!((lsm.price - american).abs() < 0.05, "{} vs {american}", lsm.price);
+    }
+
+    #[test]
+    fn bermudan_lies_between_european_and_american() {
+        let put = Vanilla::new(OptionType::Put, K);
+        let bermudan = ExerciseStyle::Bermudan(vec![0.25, 0.5, 0.75]);
+        let lsm = longstaff_schwartz_price(S, T, R, 0.0, SIGMA, &put, &bermudan, STEPS, 20_000);
+        let european = black_scholes_put(S, K, T, R, 0.0, SIGMA);
+        let american = tree(&ExerciseStyle::American);
+        assert!(lsm.lower_bound > european + 3.0 * lsm.std_error, "{} +/- {} vs {european}", lsm.lower_bound, lsm.std_error);
+        assert!(lsm.lower_bound < american - 3.0 * lsm.std_error, "{} +/- {} vs {american}", lsm.lower_bound, lsm.std_error);
+
+        let exact = tree(&bermudan);
+        assert!((lsm.lower_bound - exact).abs() < 4.0 * lsm.std_error + 0.02, "{} +/- {} vs {exact}", lsm.lower_bound, lsm.std_error);
+    }
+
+    #[test]
+    fn european_exercise_matches_black_scholes() {
+        let put = Vanilla::new(OptionType::Put, K);
+        let lsm = longstaff_schwartz_price(S, T, R, 0.0, SIGMA, &put, &ExerciseStyle::European, STEPS, 20_000);
+        let european = black_scholes_put(S, K, T, R, 0.0, SIGMA);
+        assert!((lsm.lower_bound - european).abs() < 4.0 * lsm.std_error, "{} +/- {} vs {european}", lsm.lower_bound, lsm.std_error);
+    }
+}
@@ -0,0 +1,197 @@
+use nalgebra::Complex;
+use statrs::distribution::{Normal, ContinuousCDF};
+use rand::prelude::*;
+use crate::models::black_scholes::{black_scholes_call, black_scholes_put};
+use crate::models::fourier::CharacteristicFunction;
+use crate::models::monte_carlo::{price_from_paths, simulate_paths_with};
+use crate::models::payoff::Payoff;
+
+const MAX_SERIES_TERMS: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MertonJumpParams {
+    pub lambda: f64,
+    pub mu_j: f64,
+    pub sigma_j: f64,
+}
+
+impl MertonJumpParams {
+    pub fn new(lambda: f64, mu_j: f64, sigma_j: f64) -> Self {
+        MertonJumpParams { lambda, mu_j, sigma_j }
+    }
+
+    // Expected relative jump size E[e^J - 1].
+    pub fn kappa(&self) -> f64 {
+        (self.mu_j + 0.5 * self.sigma_j * self.sigma_j).exp() - 1.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MertonJumpModel {
+    pub sigma: f64,
+    pub jumps: MertonJumpParams,
+}
+
+impl CharacteristicFunction for MertonJumpModel {
+    fn characteristic_function(&self, u: Complex<f64>, t: f64) -> Complex<f64> {
+        let i = Complex::new(0.0, 1.0);
+        let jumps = &self.jumps;
+        let jump_cf = (i * u * jumps.mu_j - 0.5 * jumps.sigma_j * jumps.sigma_j * u * u).exp();
+        let compensator = -i * u * (0.5 * self.sigma * self.sigma + jumps.lambda * jumps.kappa());
+        (t * (compensator - 0.5 * self.sigma * self.sigma * u * u + jumps.lambda * (jump_cf - 1.0))).exp()
+    }
+}
+
+// Merton (1976): a Poisson-weighted sum of Black-Scholes prices, each
+// conditioned on exactly n jumps before expiry.
+fn merton_series<F: Fn(f64, f64) -> f64>(t: f64, r: f64, sigma: f64, params: &MertonJumpParams, bs: F) -> f64 {
+    let kappa = params.kappa();
+    let lambda_prime = params.lambda * (1.0 + kappa);
+    let mean_jumps = lambda_prime * t;
+
+    let mut weight = (-mean_jumps).exp();
+    let mut total = 0.0;
+    let mut cumulative = 0.0;
+
+    for n in 0..MAX_SERIES_TERMS {
+        if n > 0 {
+            weight *= mean_jumps / n as f64;
+        }
+        let n = n as f64;
+        let sigma_n = (sigma * sigma + n * params.sigma_j * params.sigma_j / t).sqrt();
+        let r_n = r - params.lambda * kappa + n * (1.0 + kappa).ln() / t;
+        total += weight * bs(r_n, sigma_n);
+        cumulative += weight;
+        if n > mean_jumps && 1.0 - cumulative < 1e-14 {
+            break;
+        }
+    }
+
+    total
+}
+
+pub fn merton_jump_call(s: f64, k: f64, t: f64, r: f64, q: f64, sigma: f64, params: &MertonJumpParams) -> f64 {
+    merton_series(t, r, sigma, params, |r_n, sigma_n| black_scholes_call(s, k, t, r_n, q, sigma_n))
+}
+
+pub fn merton_jump_put(s: f64, k: f64, t: f64, r: f64, q: f64, sigma: f64, params: &MertonJumpParams) -> f64 {
+    merton_series(t, r, sigma, params, |r_n, sigma_n| black_scholes_put(s, k, t, r_n, q, sigma_n))
+}
+
+fn poisson_sample<R: Rng>(rng: &mut R, mean: f64) -> usize {
+    let limit = (-mean).exp();
+    let mut product = rng.random::<f64>();
+    let mut count = 0;
+    while product > limit {
+        product *= rng.random::<f64>();
+        count += 1;
+    }
+    count
+}
+
+pub fn simulate_jump_diffusion_paths(
+    s0: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    params: &MertonJumpParams,
+    num_steps: usize,
+    num_paths: usize,
+) -> Vec<Vec<f64>> {
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let dt = t / num_steps as f64;
+    let drift = (r - q - params.lambda * params.kappa() - 0.5 * sigma * sigma) * dt;
+
+    simulate_paths_with(s0, s0, num_steps, num_paths, |rng, current_price, _| {
+        let z = normal.inverse_cdf(rng.random::<f64>());
+        let mut log_return = drift + sigma * dt.sqrt() * z;
+        for _ in 0..poisson_sample(rng, params.lambda * dt) {
+            log_return += params.mu_j + params.sigma_j * normal.inverse_cdf(rng.random::<f64>());
+        }
+        *current_price *= log_return.exp();
+        *current_price
+    })
+}
+
+pub fn merton_jump_monte_carlo_price<P: Payoff>(
+    s0: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    params: &MertonJumpParams,
+    payoff: &P,
+    num_steps: usize,
+    num_paths: usize,
+) -> f64 {
+    let paths = simulate_jump_diffusion_paths(s0, t, r, q, sigma, params, num_steps, num_paths);
+    price_from_paths(&paths, t, r, payoff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::fourier::{FourierMethod, fourier_price_strikes};
+    use crate::models::payoff::{OptionType, Vanilla};
+
+    const S: f64 = 100.0;
+    const T: f64 = 1.0;
+    const R: f64 = 0.04;
+    const Q: f64 = 0.01;
+    const SIGMA: f64 = 0.2;
+    const STRIKES: [f64; 5] = [80.0, 90.0, 100.0, 110.0, 120.0];
+
+    fn jumps() -> MertonJumpParams {
+        MertonJumpParams::new(0.5, -0.1, 0.15)
+    }
+
+    fn batched<F: FnMut() -> f64>(batches: usize, mut price: F) -> (f64, f64) {
+        let samples: Vec<f64> = (0..batches).map(|_| price()).collect();
+        let mean = samples.iter().sum::<f64>() / batches as f64;
+        let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (batches - 1) as f64;
+        (mean, (variance / batches as f64).sqrt())
+    }
+
+    #[test]
+    fn series_matches_cos() {
+        let model = MertonJumpModel { sigma: SIGMA, jumps: jumps() };
+        for option_type in [OptionType::Call, OptionType::Put] {
+            let cos = fourier_price_strikes(&model, S, &STRIKES, T, R, Q, option_type, FourierMethod::Cos);
+            for (&k, &cos) in STRIKES.iter().zip(cos.iter()) {
+                let series = match option_type {
+                    OptionType::Call => merton_jump_call(S, k, T, R, Q, SIGMA, &jumps()),
+                    OptionType::Put => merton_jump_put(S, k, T, R, Q, SIGMA, &jumps()),
+                };
+                assert!((series - cos).abs() < 1e-6, "{option_type:?} {k}: {series} vs {cos}");
+            }
+        }
+    }
+
+    #[test]
+    fn series_matches_simulation() {
+        // one step is exact: the Poisson count over the whole period is sampled directly
+        for &k in &[90.0, 110.0] {
+            for option_type in [OptionType::Call, OptionType::Put] {
+                let payoff = Vanilla::new(option_type, k);
+                let (mc, se) = batched(20, || merton_jump_monte_carlo_price(S, T, R, Q, SIGMA, &jumps(), &payoff, 1, 5000));
+                let series = match option_type {
+                    OptionType::Call => merton_jump_call(S, k, T, R, Q, SIGMA, &jumps()),
+                    OptionType::Put => merton_jump_put(S, k, T, R, Q, SIGMA, &jumps()),
+                };
+                assert!((mc - series).abs() < 4.0 * se, "{option_type:?} {k}: {mc} +/- {se} vs {series}");
+            }
+        }
+    }
+
+    #[test]
+    fn no_jumps_is_black_scholes() {
+        let none = MertonJumpParams::new(0.0, -0.1, 0.15);
+        for &k in &STRIKES {
+            let call = merton_jump_call(S, k, T, R, Q, SIGMA, &none);
+            let put = merton_jump_put(S, k, T, R, Q, SIGMA, &none);
+            assert!((call - black_scholes_call(S, k, T, R, Q, SIGMA)).abs() < 1e-12, "{k}");
+            assert!((put - black_scholes_put(S, k, T, R, Q, SIGMA)).abs() < 1e-12, "{k}");
+        }
+    }
+}
@@ -1,2 +1,33 @@
+pub mod asian;
+pub mod bachelier;
+pub mod barrier;
+pub mod binomial;
+pub mod black76;
 pub mod black_scholes;
-pub mod monte_carlo;
\ No newline at end of file
+pub mod calendar;
+pub mod calibration;
+pub mod curve;
+pub mod digital;
+pub mod dividends;
+pub mod fourier;
+pub mod fx;
+pub mod greeks;
+pub mod heston;
+pub mod heston_calibration;
+pub mod implied_vol;
+pub mod local_vol;
+pub mod lookback;
+pub mod lsm;
+pub mod merton_jump;
+pub mod monte_carlo;
+pub mod multi_asset;
+pub mod nig;
+pub mod optimize;
+pub mod payoff;
+pub mod pde;
+pub mod quadrature;
+pub mod rates;
+pub mod sabr;
+pub mod svi;
+pub mod variance_gamma;
+pub mod vol_surface;
@@ -1,55 +1,391 @@
 use statrs::distribution::{Normal, ContinuousCDF};
 use rand::prelude::*;
+use rand::rngs::ThreadRng;
+use crate::models::dividends::{CashDividend, dividends_value_at, escrowed_spot};
+use crate::models::payoff::Payoff;
 use crate::visualisations::visualisations::plot_stock_paths;
 
-pub fn monte_carlo_option_price(
+pub fn simulate_gbm_paths(
     s0: f64,
-    k: f64,
     t: f64,
     r: f64,
+    q: f64,
     sigma: f64,
-    option_type: &str,
-    n: usize
-) -> f64 {
+    num_steps: usize,
+    num_paths: usize,
+) -> Vec<Vec<f64>> {
+    simulate_paths(s0, t, r, q, sigma, &[], num_steps, num_paths)
+}
+
+// Escrowed-dividend paths, the model of the closed form and the tree: the
+// spot net of the dividends still to come diffuses as a GBM, so the spot
+// drops by the cash amount at the end of the step holding each ex-date.
+pub fn simulate_gbm_paths_with_dividends(
+    s0: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    dividends: &[CashDividend],
+    num_steps: usize,
+    num_paths: usize,
+) -> Vec<Vec<f64>> {
+    simulate_paths(s0, t, r, q, sigma, dividends, num_steps, num_paths)
+}
+
+fn simulate_paths(
+    s0: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    dividends: &[CashDividend],
+    num_steps: usize,
+    num_paths: usize,
+) -> Vec<Vec<f64>> {
     let normal = Normal::new(0.0, 1.0).unwrap();
-    let drift = (r - 0.5 * sigma * sigma) * t;
-    let diffusion = sigma * t.sqrt();
+    let dt = t / num_steps as f64;
+    simulate_paths_with(s0, escrowed_spot(s0, t, r, dividends), num_steps, num_paths, |rng, escrowed, step| {
+        let z = normal.inverse_cdf(rng.random::<f64>());
+        *escrowed *= ((r - q - 0.5 * sigma * sigma) * dt + sigma * z * dt.sqrt()).exp();
+        *escrowed + dividends_value_at(step as f64 * dt, t, r, dividends)
+    })
+}
+
+// The path loop shared by every simulator. Each path starts at `s0` with its
+// own copy of `state`; `step` advances the state over step number 1, 2, ...,
+// `num_steps` and returns the spot at the end of it.
+pub fn simulate_paths_with<S: Clone, F: FnMut(&mut ThreadRng, &mut S, usize) -> f64>(
+    s0: f64,
+    state: S,
+    num_steps: usize,
+    num_paths: usize,
+    mut step: F,
+) -> Vec<Vec<f64>> {
     let mut rng = rand::rng();
+    let mut paths = Vec::with_capacity(num_paths);
 
-    let num_paths_to_plot = 50.min(n);
-    let num_steps = 100;
-    let mut paths = Vec::with_capacity(num_paths_to_plot);
-    let dt = t / num_steps as f64;
-    
-    for _ in 0..num_paths_to_plot {
+    for _ in 0..num_paths {
         let mut path = Vec::with_capacity(num_steps + 1);
         path.push(s0);
-        let mut current_price = s0;
-        
-        for _ in 0..num_steps {
-            let z = normal.inverse_cdf(rng.random::<f64>());
-            current_price *= ((r - 0.5 * sigma * sigma) * dt + sigma * z * dt.sqrt()).exp();
-            path.push(current_price);
+        let mut current = state.clone();
+
+        for i in 1..=num_steps {
+            path.push(step(&mut rng, &mut current, i));
         }
-        
+
         paths.push(path);
     }
-    
-    plot_stock_paths(s0, k, t, paths).expect("Failed to plot stock paths");
+
+    paths
+}
+
+pub fn monte_carlo_option_price<P: Payoff>(
+    s0: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    payoff: &P,
+    n: usize
+) -> f64 {
+    monte_carlo_option_price_with_dividends(s0, t, r, q, sigma, payoff, &[], n)
+}
+
+pub fn monte_carlo_option_price_with_dividends<P: Payoff>(
+    s0: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    payoff: &P,
+    dividends: &[CashDividend],
+    n: usize
+) -> f64 {
+    let paths = simulate_paths(s0, t, r, q, sigma, dividends, 100, 50.min(n));
+    plot_stock_paths(s0, payoff.strike(), t, paths, None).expect("Failed to plot stock paths");
+
+    european_monte_carlo_price_with_dividends(s0, t, r, q, sigma, payoff, dividends, n)
+}
+
+// Samples S_T directly, for payoffs that depend only on the terminal spot.
+pub fn european_monte_carlo_price<P: Payoff>(
+    s0: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    payoff: &P,
+    n: usize
+) -> f64 {
+    european_monte_carlo_price_with_dividends(s0, t, r, q, sigma, payoff, &[], n)
+}
+
+// Escrowed-dividend model, as in `simulate_gbm_paths_with_dividends`: every
+// dividend before expiry has been paid by then, so S_T is one lognormal draw
+// from the escrowed spot.
+pub fn european_monte_carlo_price_with_dividends<P: Payoff>(
+    s0: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    payoff: &P,
+    dividends: &[CashDividend],
+    n: usize
+) -> f64 {
+    let escrowed = escrowed_spot(s0, t, r, dividends);
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let mut rng = rand::rng();
 
     let payoffs: f64 = (0..n)
         .map(|_| {
-            let quantile = rng.random::<f64>();
-            let z = normal.inverse_cdf(quantile);
-            let st = s0 * (drift + diffusion * z).exp();
-            match option_type {
-                "call" => (st - k).max(0.0),
-                "put" => (k - st).max(0.0),
-                _ => 0.0,
-            }
+            let z = normal.inverse_cdf(rng.random::<f64>());
+            payoff.payoff(escrowed * ((r - q - 0.5 * sigma * sigma) * t + sigma * t.sqrt() * z).exp())
         })
         .sum();
 
     let expected_payoff = payoffs / n as f64;
     (-(r * t)).exp() * expected_payoff
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GreekMethod {
+    // differentiates each path; falls back to bumping for payoffs without a
+    // derivative, which `MonteCarloGreeks::method` reports
+    Pathwise,
+    // differentiates the lognormal density instead, so any payoff works
+    LikelihoodRatio,
+    // central differences, revaluing every path on the same normals
+    BumpAndRevalue,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MonteCarloEstimate {
+    pub value: f64,
+    pub std_error: f64,
+}
+
+// Same units as the analytic Greeks: vega per 1% vol, rho per 1% rate.
+// `method` is the estimator actually used.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MonteCarloGreeks {
+    pub method: GreekMethod,
+    pub price: MonteCarloEstimate,
+    pub delta: MonteCarloEstimate,
+    pub gamma: MonteCarloEstimate,
+    pub vega: MonteCarloEstimate,
+    pub rho: MonteCarloEstimate,
+}
+
+fn estimate(samples: &[f64]) -> MonteCarloEstimate {
+    let n = samples.len() as f64;
+    let value = samples.iter().sum::<f64>() / n;
+    let variance = samples.iter().map(|x| (x - value).powi(2)).sum::<f64>() / (n - 1.0);
+    MonteCarloEstimate { value, std_error: (variance / n).sqrt() }
+}
+
+// Price, delta, gamma, vega and rho of a European payoff from one set of
+// terminal draws, each with the standard error of its per-path estimator.
+// Pathwise gamma differentiates the pathwise delta by likelihood ratio, since
+// the payoff's second derivative is a point mass for kinked payoffs.
+pub fn monte_carlo_greeks<P: Payoff>(
+    s0: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    payoff: &P,
+    method: GreekMethod,
+    n: usize,
+) -> MonteCarloGreeks {
+    let method = match method {
+        GreekMethod::Pathwise if payoff.derivative(s0).is_none() => GreekMethod::BumpAndRevalue,
+        other => other,
+    };
+    let sqrt_t = t.sqrt();
+    let discount = (-r * t).exp();
+    let terminal = |s: f64, r: f64, sigma: f64, z: f64| s * ((r - q - 0.5 * sigma * sigma) * t + sigma * sqrt_t * z).exp();
+    let (spot_bump, vol_bump, rate_bump) = (0.01 * s0, 0.01, 0.001);
+    let mut rng = rand::rng();
+
+    let mut samples: Vec<Vec<f64>> = (0..5).map(|_| Vec::with_capacity(n)).collect();
+    for _ in 0..n {
+        let z = standard_normal(&mut rng);
+        let st = terminal(s0, r, sigma, z);
+        let value = discount * payoff.payoff(st);
+        let (delta, gamma, vega, rho) = match method {
+            GreekMethod::Pathwise => {
+                let slope = discount * payoff.derivative(st).unwrap_or(0.0) * st;
+                (
+                    slope / s0,
+                    slope / (s0 * s0) * (z / (sigma * sqrt_t) - 1.0),
+                    slope * (sqrt_t * z - sigma * t),
+                    slope * t - t * value,
+                )
+            }
+            GreekMethod::LikelihoodRatio => (
+                value * z / (s0 * sigma * sqrt_t),
+                value * (z * z - 1.0 - z * sigma * sqrt_t) / (s0 * s0 * sigma * sigma * t),
+                value * ((z * z - 1.0) / sigma - z * sqrt_t),
+                value * (z * sqrt_t / sigma - t),
+            ),
+            GreekMethod::BumpAndRevalue => {
+                let up = discount * payoff.payoff(terminal(s0 + spot_bump, r, sigma, z));
+                let down = discount * payoff.payoff(terminal(s0 - spot_bump, r, sigma, z));
+                let vol_up = discount * payoff.payoff(terminal(s0, r, sigma + vol_bump, z));
+                let vol_down = discount * payoff.payoff(terminal(s0, r, sigma - vol_bump, z));
+                let rate_up = (-(r + rate_bump) * t).exp() * payoff.payoff(terminal(s0, r + rate_bump, sigma, z));
+                let rate_down = (-(r - rate_bump) * t).exp() * payoff.payoff(terminal(s0, r - rate_bump, sigma, z));
+                (
+                    (up - down) / (2.0 * spot_bump),
+                    (up - 2.0 * value + down) / (spot_bump * spot_bump),
+                    (vol_up - vol_down) / (2.0 * vol_bump),
+                    (rate_up - rate_down) / (2.0 * rate_bump),
+                )
+            }
+        };
+        for (sample, x) in samples.iter_mut().zip([value, delta, gamma, vega / 100.0, rho / 100.0]) {
+            sample.push(x);
+        }
+    }
+
+    MonteCarloGreeks {
+        method,
+        price: estimate(&samples[0]),
+        delta: estimate(&samples[1]),
+        gamma: estimate(&samples[2]),
+        vega: estimate(&samples[3]),
+        rho: estimate(&samples[4]),
+    }
+}
+
+pub fn price_from_paths<P: Payoff>(paths: &[Vec<f64>], t: f64, r: f64, payoff: &P) -> f64 {
+    let payoffs: f64 = paths
+        .iter()
+        .map(|path| payoff.payoff(*path.last().unwrap()))
+        .sum();
+
+    (-(r * t)).exp() * payoffs / paths.len() as f64
+}
+
+pub fn standard_normal<R: Rng>(rng: &mut R) -> f64 {
+    Normal::new(0.0, 1.0).unwrap().inverse_cdf(rng.random::<f64>())
+}
+
+// Marsaglia-Tsang, with the usual U^(1/shape) boost for shape < 1.
+pub fn sample_gamma<R: Rng>(rng: &mut R, shape: f64, scale: f64) -> f64 {
+    if shape < 1.0 {
+        let u = rng.random::<f64>();
+        return sample_gamma(rng, shape + 1.0, scale) * u.powf(1.0 / shape);
+    }
+    let d = shape - 1.0 / 3.0;
+    let c = 1.0 / (9.0 * d).sqrt();
+    loop {
+        let z = standard_normal(rng);
+        let v = (1.0 + c * z).powi(3);
+        if v <= 0.0 {
+            continue;
+        }
+        let u = rng.random::<f64>();
+        if u.ln() < 0.5 * z * z + d - d * v + d * v.ln() {
+            return d * v * scale;
+        }
+    }
+}
+
+// Michael-Schucany-Haas sampler for the inverse Gaussian IG(mean, shape).
+pub fn sample_inverse_gaussian<R: Rng>(rng: &mut R, mean: f64, shape: f64) -> f64 {
+    let z = standard_normal(rng);
+    let y = z * z;
+    let x = mean + mean * mean * y / (2.0 * shape)
+        - mean / (2.0 * shape) * (4.0 * mean * shape * y + mean * mean * y * y).sqrt();
+    if rng.random::<f64>() <= mean / (mean + x) {
+        x
+    } else {
+        mean * mean / x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::black_scholes::{black_scholes_call_with_dividends, black_scholes_put_with_dividends};
+    use crate::models::calendar::TimeBasis;
+    use crate::models::digital::DigitalType;
+    use crate::models::greeks::black_scholes_greeks;
+    use crate::models::payoff::{Digital, OptionType, Vanilla};
+
+    #[test]
+    fn cash_dividends_drop_out_of_the_forward() {
+        // with almost no vol a deep in-the-money call is worth the discounted
+        // forward, which has the dividends' present value taken out
+        let dividends = [CashDividend::new(0.25, 2.0), CashDividend::new(0.75, 2.0), CashDividend::new(1.5, 2.0)];
+        let (s0, k, t, r) = (100.0, 60.0, 1.0, 0.05);
+        let price = european_monte_carlo_price_with_dividends(s0, t, r, 0.0, 1e-8, &Vanilla::new(OptionType::Call, k), &dividends, 1000);
+        let forward_value = s0 - 2.0 * ((-r * 0.25_f64).exp() + (-r * 0.75_f64).exp()) - k * (-r * t).exp();
+        assert!((price - forward_value).abs() < 1e-6, "{price} vs {forward_value}");
+    }
+
+    fn batched<F: FnMut() -> f64>(batches: usize, mut price: F) -> (f64, f64) {
+        let samples: Vec<f64> = (0..batches).map(|_| price()).collect();
+        let mean = samples.iter().sum::<f64>() / batches as f64;
+        let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (batches - 1) as f64;
+        (mean, (variance / batches as f64).sqrt())
+    }
+
+    #[test]
+    fn cash_dividends_match_the_escrowed_closed_form() {
+        let dividends = [CashDividend::new(0.2, 1.5), CashDividend::new(0.45, 1.5), CashDividend::new(0.7, 1.5), CashDividend::new(0.95, 1.5)];
+        let (s0, t, r, q, sigma) = (100.0, 1.0, 0.04, 0.01, 0.3);
+        for (option_type, k) in [(OptionType::Call, 95.0), (OptionType::Put, 100.0), (OptionType::Put, 110.0)] {
+            let payoff = Vanilla::new(option_type, k);
+            let exact = match option_type {
+                OptionType::Call => black_scholes_call_with_dividends(s0, k, t, r, q, sigma, &dividends),
+                OptionType::Put => black_scholes_put_with_dividends(s0, k, t, r, q, sigma, &dividends),
+            };
+            let (terminal, se) = batched(20, || european_monte_carlo_price_with_dividends(s0, t, r, q, sigma, &payoff, &dividends, 10_000));
+            assert!((terminal - exact).abs() < 4.0 * se, "{option_type:?} {k}: {terminal} +/- {se} vs {exact}");
+            let (paths, se) = batched(20, || {
+                price_from_paths(&simulate_gbm_paths_with_dividends(s0, t, r, q, sigma, &dividends, 20, 2000), t, r, &payoff)
+            });
+            assert!((paths - exact).abs() < 4.0 * se, "{option_type:?} {k}: {paths} +/- {se} vs {exact}");
+        }
+    }
+
+    #[test]
+    fn paths_drop_on_the_ex_date() {
+        let paths = simulate_gbm_paths_with_dividends(100.0, 1.0, 0.0, 0.0, 0.0, &[CashDividend::new(0.5, 3.0)], 4, 2);
+        for path in paths {
+            assert_eq!(path, vec![100.0, 100.0, 97.0, 97.0, 97.0]);
+        }
+    }
+
+    #[test]
+    fn greeks_agree_with_black_scholes_within_standard_error() {
+        let (s0, k, t, r, q, sigma) = (100.0, 105.0, 0.5, 0.03, 0.01, 0.25);
+        let exact = black_scholes_greeks(s0, k, t, r, q, sigma, OptionType::Call, TimeBasis::Calendar);
+        for method in [GreekMethod::Pathwise, GreekMethod::LikelihoodRatio, GreekMethod::BumpAndRevalue] {
+            let mc = monte_carlo_greeks(s0, t, r, q, sigma, &Vanilla::new(OptionType::Call, k), method, 200_000);
+            assert_eq!(mc.method, method);
+            for (name, estimate, value) in [
+                ("price", mc.price, exact.price),
+                ("delta", mc.delta, exact.delta),
+                ("gamma", mc.gamma, exact.gamma),
+                ("vega", mc.vega, exact.vega),
+                ("rho", mc.rho, exact.rho),
+            ] {
+                // bump-and-revalue also carries the central-difference bias
+                let tolerance = 5.0 * estimate.std_error + if method == GreekMethod::BumpAndRevalue { 2e-3 * value.abs() } else { 0.0 };
+                assert!((estimate.value - value).abs() < tolerance, "{method:?} {name}: {} vs {value}", estimate.value);
+            }
+        }
+    }
+
+    #[test]
+    fn pathwise_reports_its_fallback() {
+        let digital = Digital::new(OptionType::Call, 100.0, DigitalType::CashOrNothing(1.0));
+        let mc = monte_carlo_greeks(100.0, 1.0, 0.02, 0.0, 0.2, &digital, GreekMethod::Pathwise, 1000);
+        assert_eq!(mc.method, GreekMethod::BumpAndRevalue);
+    }
+}
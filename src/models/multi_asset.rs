@@ -0,0 +1,266 @@
+use nalgebra::{DMatrix, DVector};
+use crate::models::black_scholes::norm_cdf;
+use crate::models::monte_carlo::standard_normal;
+use crate::models::payoff::OptionType;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RainbowType {
+    BestOf,
+    WorstOf,
+}
+
+// Payoff at expiry as a function of every asset's terminal spot.
+pub trait MultiAssetPayoff {
+    fn payoff(&self, spots: &[f64]) -> f64;
+
+    // Number of spots the payoff reads, for payoffs tied to a fixed count.
+    fn num_assets(&self) -> Option<usize> {
+        None
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Basket {
+    pub option_type: OptionType,
+    pub weights: Vec<f64>,
+    pub strike: f64,
+}
+
+impl Basket {
+    pub fn new(option_type: OptionType, weights: Vec<f64>, strike: f64) -> Result<Self, Box<dyn std::error::Error>> {
+        if weights.is_empty() {
+            return Err("Basket needs at least one weight".into());
+        }
+        if weights.iter().any(|w| !w.is_finite()) {
+            return Err("Basket weights must be finite".into());
+        }
+        Ok(Basket { option_type, weights, strike })
+    }
+}
+
+impl MultiAssetPayoff for Basket {
+    fn payoff(&self, spots: &[f64]) -> f64 {
+        let basket: f64 = self.weights.iter().zip(spots.iter()).map(|(w, s)| w * s).sum();
+        self.option_type.intrinsic(basket, self.strike)
+    }
+
+    fn num_assets(&self) -> Option<usize> {
+        Some(self.weights.len())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rainbow {
+    pub option_type: OptionType,
+    pub rainbow_type: RainbowType,
+    pub strike: f64,
+}
+
+impl MultiAssetPayoff for Rainbow {
+    fn payoff(&self, spots: &[f64]) -> f64 {
+        let extreme = match self.rainbow_type {
+            RainbowType::BestOf => spots.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+            RainbowType::WorstOf => spots.iter().cloned().fold(f64::INFINITY, f64::min),
+        };
+        self.option_type.intrinsic(extreme, self.strike)
+    }
+}
+
+// Pays (S1 - S2 - K)^+ for a call and (K - (S1 - S2))^+ for a put; K = 0 is
+// the exchange option.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpreadOption {
+    pub option_type: OptionType,
+    pub strike: f64,
+}
+
+impl MultiAssetPayoff for SpreadOption {
+    fn payoff(&self, spots: &[f64]) -> f64 {
+        self.option_type.intrinsic(spots[0] - spots[1], self.strike)
+    }
+
+    fn num_assets(&self) -> Option<usize> {
+        Some(2)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MultiAssetResult {
+    pub price: f64,
+    pub std_error: f64,
+}
+
+// Lower Cholesky factor of a correlation matrix, rejecting anything that is
+// not a symmetric, unit-diagonal, positive-definite matrix of the right size.
+pub fn correlation_cholesky(correlation: &DMatrix<f64>, num_assets: usize) -> Result<DMatrix<f64>, Box<dyn std::error::Error>> {
+    if correlation.nrows() != num_assets || correlation.ncols() != num_assets {
+        return Err(format!("Correlation matrix must be {}x{}", num_assets, num_assets).into());
+    }
+    for i in 0..num_assets {
+        if (correlation[(i, i)] - 1.0).abs() > 1e-10 {
+            return Err("Correlation matrix must have a unit diagonal".into());
+        }
+        for j in 0..i {
+            if (correlation[(i, j)] - correlation[(j, i)]).abs() > 1e-10 {
+                return Err("Correlation matrix must be symmetric".into());
+            }
+            if correlation[(i, j)].abs() > 1.0 {
+                return Err("Correlations must lie in [-1, 1]".into());
+            }
+        }
+    }
+    correlation
+        .clone()
+        .cholesky()
+        .map(|factor| factor.l())
+        .ok_or_else(|| "Correlation matrix is not positive definite".into())
+}
+
+// Paths indexed as paths[path][asset][step], with step 0 the spot.
+pub fn simulate_correlated_gbm_paths(
+    spots: &[f64],
+    t: f64,
+    r: f64,
+    dividend_yields: &[f64],
+    sigmas: &[f64],
+    correlation: &DMatrix<f64>,
+    num_steps: usize,
+    num_paths: usize,
+) -> Result<Vec<Vec<Vec<f64>>>, Box<dyn std::error::Error>> {
+    let n = spots.len();
+    if dividend_yields.len() != n || sigmas.len() != n {
+        return Err("Spots, dividend yields and vols must have the same length".into());
+    }
+    let cholesky = correlation_cholesky(correlation, n)?;
+
+    let mut rng = rand::rng();
+    let dt = t / num_steps as f64;
+    let drifts: Vec<f64> = (0..n).map(|i| (r - dividend_yields[i] - 0.5 * sigmas[i] * sigmas[i]) * dt).collect();
+    let diffusions: Vec<f64> = sigmas.iter().map(|sigma| sigma * dt.sqrt()).collect();
+
+    let paths = (0..num_paths)
+        .map(|_| {
+            let mut path: Vec<Vec<f64>> = spots.iter().map(|&s| {
+                let mut asset = Vec::with_capacity(num_steps + 1);
+                asset.push(s);
+                asset
+            }).collect();
+            for step in 0..num_steps {
+                let z = DVector::from_iterator(n, (0..n).map(|_| standard_normal(&mut rng)));
+                let correlated = &cholesky * z;
+                for (i, asset) in path.iter_mut().enumerate() {
+                    let next = asset[step] * (drifts[i] + diffusions[i] * correlated[i]).exp();
+                    asset.push(next);
+                }
+            }
+            path
+        })
+        .collect();
+
+    Ok(paths)
+}
+
+pub fn multi_asset_monte_carlo_price<P: MultiAssetPayoff>(
+    spots: &[f64],
+    t: f64,
+    r: f64,
+    dividend_yields: &[f64],
+    sigmas: &[f64],
+    correlation: &DMatrix<f64>,
+    payoff: &P,
+    num_paths: usize,
+) -> Result<MultiAssetResult, Box<dyn std::error::Error>> {
+    if spots.is_empty() {
+        return Err("At least one asset is required".into());
+    }
+    if let Some(expected) = payoff.num_assets() {
+        if expected != spots.len() {
+            return Err(format!("Payoff reads {} assets but {} spots were given", expected, spots.len()).into());
+        }
+    }
+    // European payoffs only need the terminal spots, so one step is exact.
+    let paths = simulate_correlated_gbm_paths(spots, t, r, dividend_yields, sigmas, correlation, 1, num_paths)?;
+    let discount = (-r * t).exp();
+    let discounted: Vec<f64> = paths
+        .iter()
+        .map(|path| {
+            let terminal: Vec<f64> = path.iter().map(|asset| asset[1]).collect();
+            discount * payoff.payoff(&terminal)
+        })
+        .collect();
+
+    let n = num_paths as f64;
+    let price = discounted.iter().sum::<f64>() / n;
+    let variance = discounted.iter().map(|v| (v - price).powi(2)).sum::<f64>() / (n - 1.0);
+    Ok(MultiAssetResult { price, std_error: (variance / n).sqrt() })
+}
+
+// Margrabe: the option to exchange S2 for S1 is Black-Scholes on S1 / S2 with
+// vol sqrt(sigma1^2 + sigma2^2 - 2 rho sigma1 sigma2); rates drop out.
+pub fn margrabe_exchange_price(s1: f64, s2: f64, t: f64, q1: f64, q2: f64, sigma1: f64, sigma2: f64, rho: f64) -> f64 {
+    let sigma = (sigma1 * sigma1 + sigma2 * sigma2 - 2.0 * rho * sigma1 * sigma2).max(1e-16).sqrt();
+    let d1 = ((s1 / s2).ln() + (q2 - q1 + 0.5 * sigma * sigma) * t) / (sigma * t.sqrt());
+    let d2 = d1 - sigma * t.sqrt();
+    s1 * (-q1 * t).exp() * norm_cdf(d1) - s2 * (-q2 * t).exp() * norm_cdf(d2)
+}
+
+// Kirk's approximation: treats F2 + K as a single lognormal asset so the
+// spread option becomes a Margrabe exchange on forwards.
+pub fn kirk_spread_price(
+    s1: f64,
+    s2: f64,
+    k: f64,
+    t: f64,
+    r: f64,
+    q1: f64,
+    q2: f64,
+    sigma1: f64,
+    sigma2: f64,
+    rho: f64,
+    option_type: OptionType,
+) -> f64 {
+    let f1 = s1 * ((r - q1) * t).exp();
+    let f2 = s2 * ((r - q2) * t).exp();
+    let shifted = f2 + k;
+    let sigma2_adj = sigma2 * f2 / shifted;
+    let sigma = (sigma1 * sigma1 - 2.0 * rho * sigma1 * sigma2_adj + sigma2_adj * sigma2_adj).max(1e-16).sqrt();
+    let d1 = ((f1 / shifted).ln() + 0.5 * sigma * sigma * t) / (sigma * t.sqrt());
+    let d2 = d1 - sigma * t.sqrt();
+    let discount = (-r * t).exp();
+    match option_type {
+        OptionType::Call => discount * (f1 * norm_cdf(d1) - shifted * norm_cdf(d2)),
+        OptionType::Put => discount * (shifted * norm_cdf(-d2) - f1 * norm_cdf(-d1)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identity(n: usize) -> DMatrix<f64> {
+        DMatrix::identity(n, n)
+    }
+
+    #[test]
+    fn rejects_mismatched_dimensions() {
+        assert!(Basket::new(OptionType::Call, vec![], 100.0).is_err());
+        let basket = Basket::new(OptionType::Call, vec![0.5, 0.5], 100.0).unwrap();
+        let spread = SpreadOption { option_type: OptionType::Call, strike: 0.0 };
+        let three = [100.0, 100.0, 100.0];
+        assert!(multi_asset_monte_carlo_price(&three, 1.0, 0.0, &[0.0; 3], &[0.2; 3], &identity(3), &basket, 10).is_err());
+        assert!(multi_asset_monte_carlo_price(&three, 1.0, 0.0, &[0.0; 3], &[0.2; 3], &identity(3), &spread, 10).is_err());
+        assert!(multi_asset_monte_carlo_price(&three[..2], 1.0, 0.0, &[0.0; 2], &[0.2; 2], &identity(2), &spread, 10).is_ok());
+    }
+
+    #[test]
+    fn exchange_option_matches_margrabe() {
+        let (s1, s2, t, r, q1, q2, sigma1, sigma2, rho) = (100.0, 95.0, 1.0, 0.03, 0.01, 0.02, 0.25, 0.3, 0.4);
+        let correlation = DMatrix::from_row_slice(2, 2, &[1.0, rho, rho, 1.0]);
+        let exchange = SpreadOption { option_type: OptionType::Call, strike: 0.0 };
+        let analytic = margrabe_exchange_price(s1, s2, t, q1, q2, sigma1, sigma2, rho);
+        let mc = multi_asset_monte_carlo_price(&[s1, s2], t, r, &[q1, q2], &[sigma1, sigma2], &correlation, &exchange, 200_000).unwrap();
+        assert!((mc.price - analytic).abs() < 4.0 * mc.std_error, "{} vs {}", mc.price, analytic);
+        let kirk = kirk_spread_price(s1, s2, 0.0, t, r, q1, q2, sigma1, sigma2, rho, OptionType::Call);
+        assert!((kirk - analytic).abs() < 1e-10);
+    }
+}
@@ -0,0 +1,152 @@
+use nalgebra::{Complex, DVector};
+use crate::models::fourier::{calibrate_fourier_model, CharacteristicFunction, ModelCalibration};
+use crate::models::calibration::CalibrationQuote;
+use crate::models::monte_carlo::{price_from_paths, sample_inverse_gaussian, simulate_paths_with, standard_normal};
+use crate::models::payoff::Payoff;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NigParams {
+    pub alpha: f64,
+    pub beta: f64,
+    pub delta: f64,
+}
+
+impl NigParams {
+    pub fn new(alpha: f64, beta: f64, delta: f64) -> Self {
+        NigParams { alpha, beta, delta }
+    }
+
+    fn gamma(&self) -> f64 {
+        (self.alpha * self.alpha - self.beta * self.beta).sqrt()
+    }
+
+    // Drift correction making e^{X_t} a martingale; needs |beta + 1| < alpha.
+    pub fn omega(&self) -> f64 {
+        let shifted = self.alpha * self.alpha - (self.beta + 1.0).powi(2);
+        -self.delta * (self.gamma() - shifted.sqrt())
+    }
+}
+
+impl CharacteristicFunction for NigParams {
+    fn characteristic_function(&self, u: Complex<f64>, t: f64) -> Complex<f64> {
+        let i = Complex::new(0.0, 1.0);
+        let shifted = self.beta + i * u;
+        let exponent = self.delta * (self.gamma() - (self.alpha * self.alpha - shifted * shifted).sqrt());
+        (i * u * self.omega() * t + exponent * t).exp()
+    }
+}
+
+// NIG increments as a normal variance-mean mixture on an inverse Gaussian clock:
+// X = beta Z + sqrt(Z) W with Z ~ IG(delta dt / gamma, (delta dt)^2).
+pub fn simulate_nig_paths(
+    s0: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    params: &NigParams,
+    num_steps: usize,
+    num_paths: usize,
+) -> Vec<Vec<f64>> {
+    let dt = t / num_steps as f64;
+    let drift = (r - q + params.omega()) * dt;
+    let ig_mean = params.delta * dt / params.gamma();
+    let ig_shape = (params.delta * dt).powi(2);
+
+    simulate_paths_with(s0, s0, num_steps, num_paths, |rng, current_price, _| {
+        let z = sample_inverse_gaussian(rng, ig_mean, ig_shape);
+        let x = params.beta * z + z.sqrt() * standard_normal(rng);
+        *current_price *= (drift + x).exp();
+        *current_price
+    })
+}
+
+pub fn nig_monte_carlo_price<P: Payoff>(
+    s0: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    params: &NigParams,
+    payoff: &P,
+    num_steps: usize,
+    num_paths: usize,
+) -> f64 {
+    let paths = simulate_nig_paths(s0, t, r, q, params, num_steps, num_paths);
+    price_from_paths(&paths, t, r, payoff)
+}
+
+// Calibrates on (alpha, beta / alpha, delta) so the constraint |beta| < alpha
+// becomes a simple box bound.
+pub fn calibrate_nig(
+    s: f64,
+    q: f64,
+    quotes: &[CalibrationQuote],
+    initial: &NigParams,
+) -> Result<ModelCalibration<NigParams>, Box<dyn std::error::Error>> {
+    let build = |p: &DVector<f64>| NigParams::new(p[0], p[1] * p[0], p[2]);
+    let fit = calibrate_fourier_model(
+        s,
+        q,
+        quotes,
+        build,
+        DVector::from_vec(vec![initial.alpha, initial.beta / initial.alpha, initial.delta]),
+        &[1.5, -0.95, 0.01],
+        &[100.0, 0.95, 5.0],
+        100,
+    )?;
+    let params = build(&fit.params);
+    if !params.omega().is_finite() {
+        return Err("Calibrated NIG parameters admit no martingale correction".into());
+    }
+    Ok(ModelCalibration { params, rmse_iv: fit.rmse_iv, iterations: fit.iterations, converged: fit.converged })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::fourier::{FourierMethod, fourier_price_strikes, fourier_smile};
+    use crate::models::payoff::{OptionType, Vanilla};
+
+    const S: f64 = 100.0;
+    const T: f64 = 1.0;
+    const R: f64 = 0.04;
+    const Q: f64 = 0.01;
+    const STRIKES: [f64; 7] = [80.0, 90.0, 95.0, 100.0, 105.0, 110.0, 120.0];
+
+    fn batched<F: FnMut() -> f64>(batches: usize, mut price: F) -> (f64, f64) {
+        let samples: Vec<f64> = (0..batches).map(|_| price()).collect();
+        let mean = samples.iter().sum::<f64>() / batches as f64;
+        let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (batches - 1) as f64;
+        (mean, (variance / batches as f64).sqrt())
+    }
+
+    #[test]
+    fn characteristic_function_matches_simulation() {
+        let params = NigParams::new(10.0, -3.0, 0.2);
+        // one step is exact: the subordinator is sampled over the whole period
+        for option_type in [OptionType::Call, OptionType::Put] {
+            let prices = fourier_price_strikes(&params, S, &[90.0, 110.0], T, R, Q, option_type, FourierMethod::Cos);
+            for (&k, &cos) in [90.0, 110.0].iter().zip(prices.iter()) {
+                let payoff = Vanilla::new(option_type, k);
+                let (mc, se) = batched(20, || nig_monte_carlo_price(S, T, R, Q, &params, &payoff, 1, 5000));
+                assert!((mc - cos).abs() < 4.0 * se, "{option_type:?} {k}: {mc} +/- {se} vs {cos}");
+            }
+        }
+    }
+
+    #[test]
+    fn calibration_recovers_the_parameters() {
+        let truth = NigParams::new(10.0, -3.0, 0.2);
+        let mut quotes = Vec::new();
+        for &t in &[0.25, 1.0, 2.0] {
+            for (strike, implied_vol) in fourier_smile(&truth, S, &STRIKES, t, R, Q, FourierMethod::Cos) {
+                quotes.push(CalibrationQuote { strike, t, r: R, implied_vol });
+            }
+        }
+        let fit = calibrate_nig(S, Q, &quotes, &NigParams::new(6.0, 0.0, 0.4)).unwrap();
+        let p = fit.params;
+        assert!(fit.rmse_iv < 1e-5, "rmse {}", fit.rmse_iv);
+        assert!((p.alpha - truth.alpha).abs() < 0.05 * truth.alpha, "{p:?}");
+        assert!((p.beta - truth.beta).abs() < 0.05 * truth.beta.abs(), "{p:?}");
+        assert!((p.delta - truth.delta).abs() < 0.05 * truth.delta, "{p:?}");
+    }
+}
@@ -0,0 +1,131 @@
+use nalgebra::{DMatrix, DVector};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LmResult {
+    pub params: DVector<f64>,
+    pub residuals: DVector<f64>,
+    pub iterations: usize,
+    pub converged: bool,
+}
+
+fn clamp_to_bounds(p: &mut DVector<f64>, lower: &[f64], upper: &[f64]) {
+    for i in 0..p.len() {
+        p[i] = p[i].clamp(lower[i], upper[i]);
+    }
+}
+
+// Levenberg-Marquardt with a forward-difference Jacobian. The parameter vector
+// is projected back into [lower, upper] after every step.
+pub fn levenberg_marquardt<F: Fn(&DVector<f64>) -> DVector<f64>>(
+    residuals: F,
+    initial: DVector<f64>,
+    lower: &[f64],
+    upper: &[f64],
+    max_iterations: usize,
+) -> LmResult {
+    let n_params = initial.len();
+    let mut p = initial;
+    clamp_to_bounds(&mut p, lower, upper);
+    let mut res = residuals(&p);
+    let mut cost = res.norm_squared();
+    let mut lambda = 1e-3;
+    let mut iterations = 0;
+    let mut converged = false;
+
+    while iterations < max_iterations {
+        iterations += 1;
+
+        let mut jacobian = DMatrix::zeros(res.len(), n_params);
+        for j in 0..n_params {
+            let h = 1e-5 * p[j].abs().max(1e-2);
+            let mut bumped = p.clone();
+            bumped[j] += h;
+            let bumped_res = residuals(&bumped);
+            jacobian.set_column(j, &((bumped_res - &res) / h));
+        }
+
+        let jtj = jacobian.transpose() * &jacobian;
+        let gradient = jacobian.transpose() * &res;
+        if gradient.amax() < 1e-10 * (1.0 + cost) {
+            converged = true;
+            break;
+        }
+
+        let mut accepted = false;
+        while lambda < 1e10 {
+            let mut damped = jtj.clone();
+            for i in 0..n_params {
+                damped[(i, i)] += lambda * jtj[(i, i)].max(1e-12);
+            }
+            let step = match damped.lu().solve(&(-&gradient)) {
+                Some(step) => step,
+                None => {
+                    lambda *= 10.0;
+                    continue;
+                }
+            };
+
+            let mut candidate = &p + &step;
+            clamp_to_bounds(&mut candidate, lower, upper);
+            let candidate_res = residuals(&candidate);
+            let candidate_cost = candidate_res.norm_squared();
+
+            if candidate_cost < cost {
+                let improvement = cost - candidate_cost;
+                let step_size = (&candidate - &p).norm();
+                p = candidate;
+                res = candidate_res;
+                cost = candidate_cost;
+                lambda = (lambda / 10.0).max(1e-12);
+                accepted = true;
+                if improvement < 1e-12 * cost.max(1e-12) || step_size < 1e-8 {
+                    converged = true;
+                }
+                break;
+            }
+            lambda *= 10.0;
+        }
+
+        // a blown-up lambda means no step improved the fit: stalled, not
+        // converged
+        if !accepted || converged {
+            break;
+        }
+    }
+
+    LmResult { params: p, residuals: res, iterations, converged }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fits_an_exponential_decay() {
+        let times: Vec<f64> = (0..20).map(|i| i as f64 * 0.25).collect();
+        let data: Vec<f64> = times.iter().map(|t| 2.5 * (-0.7 * t).exp()).collect();
+        let fit = levenberg_marquardt(
+            |p| DVector::from_iterator(times.len(), times.iter().zip(data.iter()).map(|(t, y)| p[0] * (-p[1] * t).exp() - y)),
+            DVector::from_vec(vec![1.0, 0.1]),
+            &[0.0, 0.0],
+            &[10.0, 10.0],
+            200,
+        );
+        assert!(fit.converged);
+        assert!((fit.params[0] - 2.5).abs() < 1e-6 && (fit.params[1] - 0.7).abs() < 1e-6);
+    }
+
+    #[test]
+    fn stalled_search_is_not_converged() {
+        // the forward-difference slope at the kink points uphill both ways
+        let fit = levenberg_marquardt(
+            |p| DVector::from_vec(vec![p[0].abs() + 1.0]),
+            DVector::from_vec(vec![0.0]),
+            &[-1.0],
+            &[1.0],
+            50,
+        );
+        assert!(!fit.converged);
+        assert_eq!(fit.params[0], 0.0);
+    }
+}
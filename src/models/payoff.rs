@@ -0,0 +1,241 @@
+use std::fmt;
+use std::str::FromStr;
+use crate::models::digital::DigitalType;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptionType {
+    Call,
+    Put,
+}
+
+impl OptionType {
+    // +1 for calls, -1 for puts, the phi of most closed forms.
+    pub fn sign(&self) -> f64 {
+        match self {
+            OptionType::Call => 1.0,
+            OptionType::Put => -1.0,
+        }
+    }
+
+    pub fn intrinsic(&self, spot: f64, strike: f64) -> f64 {
+        (self.sign() * (spot - strike)).max(0.0)
+    }
+}
+
+impl fmt::Display for OptionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OptionType::Call => write!(f, "call"),
+            OptionType::Put => write!(f, "put"),
+        }
+    }
+}
+
+impl FromStr for OptionType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "call" => Ok(OptionType::Call),
+            "put" => Ok(OptionType::Put),
+            other => Err(format!("unknown option type \"{}\", expected \"call\" or \"put\"", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExerciseStyle {
+    European,
+    American,
+    Bermudan(Vec<f64>),
+}
+
+impl ExerciseStyle {
+    // Which of the `num_steps + 1` equally spaced dates on [0, t] allow
+    // exercise. Bermudan dates snap to the nearest step after today; expiry
+    // always does.
+    pub fn exercise_steps(&self, t: f64, num_steps: usize) -> Vec<bool> {
+        let mut exercisable = vec![false; num_steps + 1];
+        match self {
+            ExerciseStyle::European => {}
+            ExerciseStyle::American => exercisable.fill(true),
+            ExerciseStyle::Bermudan(dates) => {
+                for &date in dates {
+                    if date > 0.0 && date <= t {
+                        let step = ((date / t) * num_steps as f64).round() as usize;
+                        exercisable[step.clamp(1, num_steps)] = true;
+                    }
+                }
+            }
+        }
+        exercisable[num_steps] = true;
+        exercisable
+    }
+}
+
+// Payoff at expiry as a function of the terminal spot. `strike` is the
+// reference level numerical engines use to centre grids and scale regressions.
+// `derivative` is the slope in the spot, for pathwise Greeks; payoffs that
+// jump (digitals) or whose slope is unknown (closures) return None.
+// `vanilla` exposes plain calls and puts so callers can take the closed form.
+pub trait Payoff {
+    fn payoff(&self, spot: f64) -> f64;
+
+    fn strike(&self) -> f64;
+
+    fn derivative(&self, _spot: f64) -> Option<f64> {
+        None
+    }
+
+    fn vanilla(&self) -> Option<Vanilla> {
+        None
+    }
+
+    fn label(&self) -> String {
+        format!("Custom (K=${:.2})", self.strike())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Vanilla {
+    pub option_type: OptionType,
+    pub strike: f64,
+}
+
+impl Vanilla {
+    pub fn new(option_type: OptionType, strike: f64) -> Self {
+        Vanilla { option_type, strike }
+    }
+}
+
+impl Payoff for Vanilla {
+    fn payoff(&self, spot: f64) -> f64 {
+        self.option_type.intrinsic(spot, self.strike)
+    }
+
+    fn strike(&self) -> f64 {
+        self.strike
+    }
+
+    fn derivative(&self, spot: f64) -> Option<f64> {
+        let phi = self.option_type.sign();
+        Some(if phi * (spot - self.strike) > 0.0 { phi } else { 0.0 })
+    }
+
+    fn vanilla(&self) -> Option<Vanilla> {
+        Some(*self)
+    }
+
+    fn label(&self) -> String {
+        match self.option_type {
+            OptionType::Call => format!("Call (K=${:.2})", self.strike),
+            OptionType::Put => format!("Put (K=${:.2})", self.strike),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Digital {
+    pub option_type: OptionType,
+    pub strike: f64,
+    pub digital_type: DigitalType,
+}
+
+impl Digital {
+    pub fn new(option_type: OptionType, strike: f64, digital_type: DigitalType) -> Self {
+        Digital { option_type, strike, digital_type }
+    }
+}
+
+impl Payoff for Digital {
+    fn payoff(&self, spot: f64) -> f64 {
+        if self.option_type.sign() * (spot - self.strike) <= 0.0 {
+            return 0.0;
+        }
+        match self.digital_type {
+            DigitalType::CashOrNothing(cash) => cash,
+            DigitalType::AssetOrNothing => spot,
+        }
+    }
+
+    fn strike(&self) -> f64 {
+        self.strike
+    }
+
+    fn label(&self) -> String {
+        let kind = match self.digital_type {
+            DigitalType::CashOrNothing(_) => "Cash-or-Nothing",
+            DigitalType::AssetOrNothing => "Asset-or-Nothing",
+        };
+        match self.option_type {
+            OptionType::Call => format!("{} Call (K=${:.2})", kind, self.strike),
+            OptionType::Put => format!("{} Put (K=${:.2})", kind, self.strike),
+        }
+    }
+}
+
+// Vertical spread: long the option struck nearer the money, short the other,
+// so a call spread pays (S - K1)^+ - (S - K2)^+ and a put spread
+// (K2 - S)^+ - (K1 - S)^+ with K1 < K2.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spread {
+    pub option_type: OptionType,
+    pub lower_strike: f64,
+    pub upper_strike: f64,
+}
+
+impl Spread {
+    pub fn new(option_type: OptionType, lower_strike: f64, upper_strike: f64) -> Self {
+        Spread { option_type, lower_strike: lower_strike.min(upper_strike), upper_strike: upper_strike.max(lower_strike) }
+    }
+}
+
+impl Payoff for Spread {
+    fn payoff(&self, spot: f64) -> f64 {
+        match self.option_type {
+            OptionType::Call => {
+                OptionType::Call.intrinsic(spot, self.lower_strike) - OptionType::Call.intrinsic(spot, self.upper_strike)
+            }
+            OptionType::Put => {
+                OptionType::Put.intrinsic(spot, self.upper_strike) - OptionType::Put.intrinsic(spot, self.lower_strike)
+            }
+        }
+    }
+
+    fn strike(&self) -> f64 {
+        0.5 * (self.lower_strike + self.upper_strike)
+    }
+
+    fn derivative(&self, spot: f64) -> Option<f64> {
+        let inside = spot > self.lower_strike && spot < self.upper_strike;
+        Some(if inside { self.option_type.sign() } else { 0.0 })
+    }
+
+    fn label(&self) -> String {
+        match self.option_type {
+            OptionType::Call => format!("Call Spread (K=${:.2}/${:.2})", self.lower_strike, self.upper_strike),
+            OptionType::Put => format!("Put Spread (K=${:.2}/${:.2})", self.lower_strike, self.upper_strike),
+        }
+    }
+}
+
+pub struct CustomPayoff<F: Fn(f64) -> f64> {
+    pub strike: f64,
+    pub function: F,
+}
+
+impl<F: Fn(f64) -> f64> CustomPayoff<F> {
+    pub fn new(strike: f64, function: F) -> Self {
+        CustomPayoff { strike, function }
+    }
+}
+
+impl<F: Fn(f64) -> f64> Payoff for CustomPayoff<F> {
+    fn payoff(&self, spot: f64) -> f64 {
+        (self.function)(spot)
+    }
+
+    fn strike(&self) -> f64 {
+        self.strike
+    }
+}
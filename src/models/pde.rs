@@ -0,0 +1,280 @@
+use crate::models::payoff::{ExerciseStyle, Payoff};
+use crate::models::calendar::TimeBasis;
+
+const RANNACHER_STEPS: usize = 4;
+const PSOR_OMEGA: f64 = 1.2;
+const PSOR_TOLERANCE: f64 = 1e-9;
+const PSOR_MAX_ITERATIONS: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PdeBarrier {
+    pub lower: Option<f64>,
+    pub upper: Option<f64>,
+    pub rebate: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PdeGrid {
+    pub spots: Vec<f64>,
+    pub times_to_expiry: Vec<f64>,
+    pub values: Vec<Vec<f64>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PdeResult {
+    pub price: f64,
+    pub delta: f64,
+    pub gamma: f64,
+    pub theta: f64,
+    pub grid: PdeGrid,
+}
+
+impl PdeGrid {
+    // Time layer closest to `tau` years before expiry. The Rannacher start
+    // takes half steps, so layers are not evenly spaced.
+    pub fn layer_at(&self, tau: f64) -> usize {
+        (0..self.times_to_expiry.len())
+            .min_by(|&a, &b| (self.times_to_expiry[a] - tau).abs().total_cmp(&(self.times_to_expiry[b] - tau).abs()))
+            .unwrap()
+    }
+
+    // Value, delta and gamma at `s` on time layer `layer`, from the quadratic
+    // through the three nodes closest to `s`.
+    pub fn value_at(&self, layer: usize, s: f64) -> (f64, f64, f64) {
+        let values = &self.values[layer];
+        let n = self.spots.len() - 1;
+        let ds = self.spots[1] - self.spots[0];
+        let i = (((s - self.spots[0]) / ds).round() as usize).clamp(1, n - 1);
+        let delta = (values[i + 1] - values[i - 1]) / (2.0 * ds);
+        let gamma = (values[i + 1] - 2.0 * values[i] + values[i - 1]) / (ds * ds);
+        let x = s - self.spots[i];
+        (values[i] + delta * x + 0.5 * gamma * x * x, delta + gamma * x, gamma)
+    }
+}
+
+fn solve_tridiagonal(lower: &[f64], diag: &[f64], upper: &[f64], rhs: &[f64]) -> Vec<f64> {
+    let n = diag.len();
+    let mut c = vec![0.0; n];
+    let mut d = vec![0.0; n];
+    c[0] = upper[0] / diag[0];
+    d[0] = rhs[0] / diag[0];
+    for i in 1..n {
+        let m = diag[i] - lower[i] * c[i - 1];
+        c[i] = upper[i] / m;
+        d[i] = (rhs[i] - lower[i] * d[i - 1]) / m;
+    }
+    let mut x = vec![0.0; n];
+    x[n - 1] = d[n - 1];
+    for i in (0..n - 1).rev() {
+        x[i] = d[i] - c[i] * x[i + 1];
+    }
+    x
+}
+
+// Projected SOR for the linear complementarity problem A x >= b, x >= floor.
+fn solve_psor(lower: &[f64], diag: &[f64], upper: &[f64], rhs: &[f64], floor: &[f64], guess: &[f64]) -> Vec<f64> {
+    let n = diag.len();
+    let mut x: Vec<f64> = guess.iter().zip(floor.iter()).map(|(g, f)| g.max(*f)).collect();
+    for _ in 0..PSOR_MAX_ITERATIONS {
+        let mut error = 0.0;
+        for i in 0..n {
+            let mut residual = rhs[i] - diag[i] * x[i];
+            if i > 0 {
+                residual -= lower[i] * x[i - 1];
+            }
+            if i + 1 < n {
+                residual -= upper[i] * x[i + 1];
+            }
+            let updated = (x[i] + PSOR_OMEGA * residual / diag[i]).max(floor[i]);
+            error += (updated - x[i]).powi(2);
+            x[i] = updated;
+        }
+        if error.sqrt() < PSOR_TOLERANCE {
+            break;
+        }
+    }
+    x
+}
+
+// Solves the Black-Scholes PDE in time-to-expiry on a uniform spot grid with
+// Crank-Nicolson. The first steps are replaced by implicit Euler half-steps
+// (Rannacher smoothing) so the payoff kink does not pollute delta and gamma.
+// Knock-out barriers become Dirichlet boundaries paying the rebate; otherwise
+// the payoff is taken to be linear beyond the grid, a + b S, which discounts
+// to a e^{-r tau} + b S e^{-q tau}.
+pub fn crank_nicolson_price<P: Payoff>(
+    s: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    payoff: &P,
+    exercise: &ExerciseStyle,
+    barrier: Option<PdeBarrier>,
+    num_spot_steps: usize,
+    num_time_steps: usize,
+    basis: TimeBasis,
+) -> PdeResult {
+    let k = payoff.strike();
+    let american = matches!(exercise, ExerciseStyle::American);
+    let payoff = |spot: f64| payoff.payoff(spot);
+
+    let lower_barrier = barrier.and_then(|b| b.lower);
+    let upper_barrier = barrier.and_then(|b| b.upper);
+    let rebate = barrier.map(|b| b.rebate).unwrap_or(0.0);
+    let s_min = lower_barrier.unwrap_or(0.0);
+    let s_max = upper_barrier.unwrap_or_else(|| s.max(k) * (5.0 * sigma * t.sqrt()).exp().max(2.0));
+
+    let n = num_spot_steps.max(3);
+    let ds = (s_max - s_min) / n as f64;
+    let spots: Vec<f64> = (0..=n).map(|i| s_min + i as f64 * ds).collect();
+
+    let mut current: Vec<f64> = spots.iter().map(|&spot| payoff(spot)).collect();
+    if lower_barrier.is_some() {
+        current[0] = rebate;
+    }
+    if upper_barrier.is_some() {
+        current[n] = rebate;
+    }
+
+    let boundary = |tau: f64, spot: f64, at_lower: bool| {
+        if (at_lower && lower_barrier.is_some()) || (!at_lower && upper_barrier.is_some()) {
+            return rebate;
+        }
+        let neighbour = if at_lower { spot + ds } else { spot - ds };
+        let slope = (payoff(spot) - payoff(neighbour)) / (spot - neighbour);
+        let intercept = payoff(spot) - slope * spot;
+        let european = intercept * (-r * tau).exp() + slope * spot * (-q * tau).exp();
+        if american {
+            european.max(payoff(spot))
+        } else {
+            european
+        }
+    };
+
+    let alpha: Vec<f64> = spots.iter().map(|&x| 0.5 * sigma * sigma * x * x / (ds * ds)).collect();
+    let beta: Vec<f64> = spots.iter().map(|&x| (r - q) * x / (2.0 * ds)).collect();
+    let floor: Vec<f64> = spots[1..n].iter().map(|&spot| payoff(spot)).collect();
+
+    let num_time_steps = num_time_steps.max(RANNACHER_STEPS);
+    let dt = t / num_time_steps as f64;
+    // indexed by steps from today, so layer m (tau = m dt) is step N - m
+    let exercisable = exercise.exercise_steps(t, num_time_steps);
+    let mut schedule = vec![(0.5 * dt, 1.0); RANNACHER_STEPS];
+    schedule.extend(vec![(dt, 0.5); num_time_steps - RANNACHER_STEPS / 2]);
+
+    let mut times_to_expiry = vec![0.0];
+    let mut values = vec![current.clone()];
+    let mut tau = 0.0;
+
+    for (step, &(h, theta)) in schedule.iter().enumerate() {
+        let next_tau = tau + h;
+        let m = n - 1;
+        let mut lower = vec![0.0; m];
+        let mut diag = vec![0.0; m];
+        let mut upper = vec![0.0; m];
+        let mut rhs = vec![0.0; m];
+
+        for i in 1..n {
+            let (l, c, u) = (alpha[i] - beta[i], -2.0 * alpha[i] - r, alpha[i] + beta[i]);
+            let explicit = current[i] + (1.0 - theta) * h * (l * current[i - 1] + c * current[i] + u * current[i + 1]);
+            lower[i - 1] = -theta * h * l;
+            diag[i - 1] = 1.0 - theta * h * c;
+            upper[i - 1] = -theta * h * u;
+            rhs[i - 1] = explicit;
+        }
+
+        let left = boundary(next_tau, spots[0], true);
+        let right = boundary(next_tau, spots[n], false);
+        rhs[0] -= lower[0] * left;
+        rhs[m - 1] -= upper[m - 1] * right;
+
+        let interior = if american {
+            solve_psor(&lower, &diag, &upper, &rhs, &floor, &current[1..n])
+        } else {
+            solve_tridiagonal(&lower, &diag, &upper, &rhs)
+        };
+
+        current[0] = left;
+        current[n] = right;
+        current[1..n].copy_from_slice(&interior);
+        tau = next_tau;
+
+        // Rannacher half-steps are only recorded at whole time steps.
+        if step >= RANNACHER_STEPS || step % 2 == 1 {
+            let layer = times_to_expiry.len();
+            if !american && exercisable[num_time_steps - layer] {
+                for (value, &exercise_value) in current[1..n].iter_mut().zip(floor.iter()) {
+                    *value = value.max(exercise_value);
+                }
+            }
+            times_to_expiry.push(tau);
+            values.push(current.clone());
+        }
+    }
+
+    let grid = PdeGrid { spots, times_to_expiry, values };
+    let last = grid.values.len() - 1;
+    let (price, delta, gamma) = grid.value_at(last, s);
+    let (previous, _, _) = grid.value_at(last - 1, s);
+    let theta = -(price - previous) / (grid.times_to_expiry[last] - grid.times_to_expiry[last - 1]) / basis.days_per_year();
+
+    PdeResult { price, delta, gamma, theta, grid }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::barrier::{barrier_option_price, BarrierType};
+    use crate::models::binomial::{binomial_option_price, TreeMethod};
+    use crate::models::greeks::black_scholes_greeks;
+    use crate::models::payoff::{OptionType, Vanilla};
+
+    const S: f64 = 100.0;
+    const T: f64 = 1.0;
+    const R: f64 = 0.05;
+    const Q: f64 = 0.02;
+    const SIGMA: f64 = 0.3;
+
+    #[test]
+    fn european_matches_black_scholes() {
+        for option_type in [OptionType::Call, OptionType::Put] {
+            let payoff = Vanilla::new(option_type, 105.0);
+            let pde = crank_nicolson_price(S, T, R, Q, SIGMA, &payoff, &ExerciseStyle::European, None, 400, 400, TimeBasis::Calendar);
+            let exact = black_scholes_greeks(S, 105.0, T, R, Q, SIGMA, option_type, TimeBasis::Calendar);
+            assert!((pde.price - exact.price).abs() < 1e-2, "{option_type:?} price {} vs {}", pde.price, exact.price);
+            assert!((pde.delta - exact.delta).abs() < 1e-3, "{option_type:?} delta {} vs {}", pde.delta, exact.delta);
+            assert!((pde.gamma - exact.gamma).abs() < 1e-4, "{option_type:?} gamma {} vs {}", pde.gamma, exact.gamma);
+            assert!((pde.theta - exact.theta).abs() < 1e-3, "{option_type:?} theta {} vs {}", pde.theta, exact.theta);
+        }
+    }
+
+    #[test]
+    fn american_put_matches_the_tree() {
+        let payoff = Vanilla::new(OptionType::Put, 110.0);
+        let pde = crank_nicolson_price(S, T, R, Q, SIGMA, &payoff, &ExerciseStyle::American, None, 400, 400, TimeBasis::Calendar);
+        let tree = binomial_option_price(S, T, R, Q, SIGMA, &payoff, &ExerciseStyle::American, 1001, TreeMethod::LeisenReimer, TimeBasis::Calendar);
+        let european = black_scholes_greeks(S, 110.0, T, R, Q, SIGMA, OptionType::Put, TimeBasis::Calendar).price;
+        assert!(pde.price > european);
+        assert!((pde.price - tree.price).abs() < 2e-2, "{} vs {}", pde.price, tree.price);
+    }
+
+    #[test]
+    fn knock_out_matches_the_analytic_barrier() {
+        let payoff = Vanilla::new(OptionType::Call, 100.0);
+        let barrier = PdeBarrier { lower: Some(85.0), upper: None, rebate: 0.0 };
+        let pde = crank_nicolson_price(S, T, R, Q, SIGMA, &payoff, &ExerciseStyle::European, Some(barrier), 400, 400, TimeBasis::Calendar);
+        let analytic = barrier_option_price(S, 100.0, 85.0, 0.0, T, R, Q, SIGMA, OptionType::Call, BarrierType::DownAndOut);
+        assert!((pde.price - analytic).abs() < 2e-2, "{} vs {}", pde.price, analytic);
+    }
+
+    #[test]
+    fn layers_are_found_by_time_to_expiry() {
+        let payoff = Vanilla::new(OptionType::Call, 100.0);
+        let pde = crank_nicolson_price(S, 0.1, R, Q, SIGMA, &payoff, &ExerciseStyle::European, None, 100, 10, TimeBasis::Calendar);
+        for tau in [0.0, 0.03, 0.07, 0.1] {
+            let layer = pde.grid.layer_at(tau);
+            assert!((pde.grid.times_to_expiry[layer] - tau).abs() < 1e-12);
+        }
+    }
+}
@@ -0,0 +1,69 @@
+// Bisection depth at which an interval is accepted whatever its error
+// estimate; 20 levels is a width of about a millionth of the range.
+const MAX_DEPTH: usize = 20;
+
+// `converged` is false when some interval hit `MAX_DEPTH` before meeting its
+// share of the tolerance.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuadratureResult {
+    pub value: f64,
+    pub converged: bool,
+}
+
+fn simpson(fa: f64, fm: f64, fb: f64, a: f64, b: f64) -> f64 {
+    (b - a) / 6.0 * (fa + 4.0 * fm + fb)
+}
+
+fn adaptive_simpson_step<F: Fn(f64) -> f64>(
+    f: &F,
+    a: f64,
+    b: f64,
+    fa: f64,
+    fm: f64,
+    fb: f64,
+    whole: f64,
+    tol: f64,
+    depth: usize,
+) -> QuadratureResult {
+    let m = 0.5 * (a + b);
+    let lm = 0.5 * (a + m);
+    let rm = 0.5 * (m + b);
+    let flm = f(lm);
+    let frm = f(rm);
+    let left = simpson(fa, flm, fm, a, m);
+    let right = simpson(fm, frm, fb, m, b);
+    let delta = left + right - whole;
+    if depth == 0 || delta.abs() <= 15.0 * tol {
+        return QuadratureResult { value: left + right + delta / 15.0, converged: delta.abs() <= 15.0 * tol };
+    }
+    let left = adaptive_simpson_step(f, a, m, fa, flm, fm, left, 0.5 * tol, depth - 1);
+    let right = adaptive_simpson_step(f, m, b, fm, frm, fb, right, 0.5 * tol, depth - 1);
+    QuadratureResult { value: left.value + right.value, converged: left.converged && right.converged }
+}
+
+pub fn adaptive_simpson<F: Fn(f64) -> f64>(f: F, a: f64, b: f64, tol: f64) -> QuadratureResult {
+    let fa = f(a);
+    let fb = f(b);
+    let fm = f(0.5 * (a + b));
+    let whole = simpson(fa, fm, fb, a, b);
+    adaptive_simpson_step(&f, a, b, fa, fm, fb, whole, tol, MAX_DEPTH)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integrates_smooth_functions() {
+        let result = adaptive_simpson(|x: f64| x.sin(), 0.0, std::f64::consts::PI, 1e-10);
+        assert!(result.converged);
+        assert!((result.value - 2.0).abs() < 1e-9, "{}", result.value);
+    }
+
+    #[test]
+    fn reports_when_the_depth_runs_out() {
+        // the oscillation is far finer than 20 bisections can resolve
+        let result = adaptive_simpson(|x: f64| (1e9 * x).sin(), 0.0, 1.0, 1e-12);
+        assert!(!result.converged);
+    }
+}
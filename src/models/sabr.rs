@@ -0,0 +1,229 @@
+use nalgebra::DVector;
+use crate::models::optimize::levenberg_marquardt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SabrParams {
+    pub alpha: f64,
+    pub beta: f64,
+    pub rho: f64,
+    pub nu: f64,
+}
+
+impl SabrParams {
+    pub fn new(alpha: f64, beta: f64, rho: f64, nu: f64) -> Self {
+        SabrParams { alpha, beta, rho, nu }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SabrFormula {
+    Hagan,
+    Obloj,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SabrCalibration {
+    pub params: SabrParams,
+    pub rmse: f64,
+    pub errors: Vec<f64>,
+    pub iterations: usize,
+    pub converged: bool,
+}
+
+fn x_of_z(z: f64, rho: f64) -> f64 {
+    (((1.0 - 2.0 * rho * z + z * z).sqrt() + z - rho) / (1.0 - rho)).ln()
+}
+
+fn z_over_x(z: f64, rho: f64) -> f64 {
+    if z.abs() < 1e-7 {
+        1.0 - 0.5 * rho * z
+    } else {
+        z / x_of_z(z, rho)
+    }
+}
+
+fn time_correction(f: f64, k: f64, t: f64, p: &SabrParams) -> f64 {
+    let omb = 1.0 - p.beta;
+    let fk_pow = (f * k).powf(0.5 * omb);
+    1.0 + (omb * omb * p.alpha * p.alpha / (24.0 * fk_pow * fk_pow)
+        + p.rho * p.beta * p.nu * p.alpha / (4.0 * fk_pow)
+        + (2.0 - 3.0 * p.rho * p.rho) * p.nu * p.nu / 24.0)
+        * t
+}
+
+pub fn sabr_lognormal_vol(f: f64, k: f64, t: f64, p: &SabrParams) -> f64 {
+    let omb = 1.0 - p.beta;
+    let log_fk = (f / k).ln();
+    let fk_pow = (f * k).powf(0.5 * omb);
+    let denominator = fk_pow
+        * (1.0 + omb * omb * log_fk * log_fk / 24.0 + omb.powi(4) * log_fk.powi(4) / 1920.0);
+    let z = p.nu / p.alpha * fk_pow * log_fk;
+    p.alpha / denominator * z_over_x(z, p.rho) * time_correction(f, k, t, p)
+}
+
+// Obloj (2008) replaces Hagan's leading-order term with one that stays
+// accurate for strikes far from the forward and as beta approaches zero.
+pub fn sabr_lognormal_vol_obloj(f: f64, k: f64, t: f64, p: &SabrParams) -> f64 {
+    let omb = 1.0 - p.beta;
+    let log_fk = (f / k).ln();
+    if log_fk.abs() < 1e-12 {
+        return p.alpha / f.powf(omb) * time_correction(f, k, t, p);
+    }
+
+    let integral = if omb.abs() < 1e-12 {
+        log_fk
+    } else {
+        // (f^omb - k^omb) / omb, without the cancellation as k approaches f
+        -f.powf(omb) * (-omb * log_fk).exp_m1() / omb
+    };
+    let z = p.nu / p.alpha * integral;
+    let leading = p.alpha * log_fk / integral * z_over_x(z, p.rho);
+    leading * time_correction(f, k, t, p)
+}
+
+pub fn sabr_normal_vol(f: f64, k: f64, t: f64, p: &SabrParams) -> f64 {
+    let omb = 1.0 - p.beta;
+    let log_fk = (f / k).ln();
+    let fk_pow = (f * k).powf(0.5 * omb);
+    let numerator = 1.0 + log_fk * log_fk / 24.0 + log_fk.powi(4) / 1920.0;
+    let denominator = 1.0 + omb * omb * log_fk * log_fk / 24.0 + omb.powi(4) * log_fk.powi(4) / 1920.0;
+    let z = p.nu / p.alpha * fk_pow * log_fk;
+    let correction = 1.0
+        + (-p.beta * (2.0 - p.beta) * p.alpha * p.alpha / (24.0 * fk_pow * fk_pow)
+            + p.rho * p.alpha * p.nu * p.beta / (4.0 * fk_pow)
+            + (2.0 - 3.0 * p.rho * p.rho) * p.nu * p.nu / 24.0)
+            * t;
+    p.alpha * (f * k).powf(0.5 * p.beta) * numerator / denominator * z_over_x(z, p.rho) * correction
+}
+
+pub fn sabr_vol(f: f64, k: f64, t: f64, p: &SabrParams, formula: SabrFormula) -> f64 {
+    match formula {
+        SabrFormula::Hagan => sabr_lognormal_vol(f, k, t, p),
+        SabrFormula::Obloj => sabr_lognormal_vol_obloj(f, k, t, p),
+    }
+}
+
+pub fn sabr_smile(f: f64, t: f64, p: &SabrParams, formula: SabrFormula, strikes: &[f64]) -> Vec<(f64, f64)> {
+    strikes.iter().map(|&k| (k, sabr_vol(f, k, t, p, formula))).collect()
+}
+
+// Fits alpha, rho and nu to one expiry's lognormal implied vols with beta held
+// fixed, as is market practice.
+pub fn calibrate_sabr(
+    f: f64,
+    t: f64,
+    strikes: &[f64],
+    vols: &[f64],
+    beta: f64,
+    formula: SabrFormula,
+) -> Result<SabrCalibration, Box<dyn std::error::Error>> {
+    if strikes.len() != vols.len() {
+        return Err("Strikes and vols must have the same length".into());
+    }
+    if strikes.len() < 3 {
+        return Err("Need at least 3 strikes to calibrate SABR".into());
+    }
+
+    let atm_vol = strikes
+        .iter()
+        .zip(vols.iter())
+        .min_by(|a, b| (a.0 - f).abs().partial_cmp(&(b.0 - f).abs()).unwrap())
+        .map(|(_, &v)| v)
+        .unwrap();
+    let initial = DVector::from_vec(vec![atm_vol * f.powf(1.0 - beta), 0.0, 0.5]);
+
+    let residuals = |p: &DVector<f64>| {
+        let params = SabrParams::new(p[0], beta, p[1], p[2]);
+        DVector::from_iterator(
+            strikes.len(),
+            strikes.iter().zip(vols.iter()).map(|(&k, &v)| sabr_vol(f, k, t, &params, formula) - v),
+        )
+    };
+
+    let fit = levenberg_marquardt(residuals, initial, &[1e-6, -0.999, 1e-6], &[10.0 * f.powf(1.0 - beta), 0.999, 10.0], 200);
+    let params = SabrParams::new(fit.params[0], beta, fit.params[1], fit.params[2]);
+    let errors: Vec<f64> = fit.residuals.iter().cloned().collect();
+    let rmse = (errors.iter().map(|e| e * e).sum::<f64>() / errors.len() as f64).sqrt();
+
+    Ok(SabrCalibration {
+        params,
+        rmse,
+        errors,
+        iterations: fit.iterations,
+        converged: fit.converged,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::bachelier::bachelier_implied_vol;
+    use crate::models::black76::black76_price;
+    use crate::models::payoff::OptionType;
+
+    const F: f64 = 0.03;
+    const T: f64 = 1.5;
+
+    fn params() -> SabrParams {
+        SabrParams::new(0.035, 0.5, -0.3, 0.4)
+    }
+
+    fn strikes() -> Vec<f64> {
+        (0..=10).map(|i| F * (0.6 + 0.08 * i as f64)).collect()
+    }
+
+    #[test]
+    fn hagan_and_obloj_agree_at_the_money() {
+        let p = params();
+        let hagan = sabr_lognormal_vol(F, F, T, &p);
+        let obloj = sabr_lognormal_vol_obloj(F, F, T, &p);
+        assert!((hagan - obloj).abs() < 1e-12, "{hagan} vs {obloj}");
+
+        // both are continuous through the F = K limit
+        for bump in [1e-6, 1e-9, 1e-12] {
+            for k in [F * (1.0 + bump), F * (1.0 - bump)] {
+                assert!((sabr_lognormal_vol(F, k, T, &p) - hagan).abs() < 1e-5, "Hagan at {k}");
+                assert!((sabr_lognormal_vol_obloj(F, k, T, &p) - obloj).abs() < 1e-5, "Obloj at {k}");
+            }
+        }
+
+        // and only drift apart away from the money
+        for k in [0.95 * F, 1.05 * F] {
+            let (hagan, obloj) = (sabr_lognormal_vol(F, k, T, &p), sabr_lognormal_vol_obloj(F, k, T, &p));
+            assert!((hagan - obloj).abs() < 1e-3, "{k}: {hagan} vs {obloj}");
+        }
+    }
+
+    #[test]
+    fn normal_vol_matches_the_converted_lognormal_vol() {
+        let p = params();
+        for k in strikes() {
+            let option_type = if k < F { OptionType::Put } else { OptionType::Call };
+            let price = black76_price(F, k, T, 0.0, sabr_lognormal_vol(F, k, T, &p), option_type);
+            let converted = bachelier_implied_vol(price, F, k, T, 0.0, option_type).unwrap();
+            let normal = sabr_normal_vol(F, k, T, &p);
+            assert!((normal - converted).abs() < 0.01 * converted, "{k}: {normal} vs {converted}");
+        }
+    }
+
+    #[test]
+    fn calibration_recovers_the_parameters() {
+        let truth = params();
+        let strikes = strikes();
+        for formula in [SabrFormula::Hagan, SabrFormula::Obloj] {
+            let vols: Vec<f64> = strikes.iter().map(|&k| sabr_vol(F, k, T, &truth, formula)).collect();
+            let fit = calibrate_sabr(F, T, &strikes, &vols, truth.beta, formula).unwrap();
+            let p = fit.params;
+            assert!(fit.rmse < 1e-6, "{formula:?}: rmse {}", fit.rmse);
+            assert!((p.alpha - truth.alpha).abs() < 1e-4, "{formula:?}: {p:?}");
+            assert!((p.rho - truth.rho).abs() < 1e-3, "{formula:?}: {p:?}");
+            assert!((p.nu - truth.nu).abs() < 1e-3, "{formula:?}: {p:?}");
+        }
+    }
+
+    #[test]
+    fn mismatched_inputs_are_errors() {
+        assert!(calibrate_sabr(F, T, &[0.02, 0.03, 0.04], &[0.2, 0.2], 0.5, SabrFormula::Hagan).is_err());
+        assert!(calibrate_sabr(F, T, &[0.02, 0.03], &[0.2, 0.2], 0.5, SabrFormula::Hagan).is_err());
+    }
+}
@@ -0,0 +1,418 @@
+use nalgebra::DVector;
+use crate::models::optimize::levenberg_marquardt;
+use crate::models::vol_surface::VolatilitySurface;
+
+// One expiry's quotes, with the zero rate to that expiry and the forward
+// implied by it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SmileSlice {
+    pub t: f64,
+    pub r: f64,
+    pub forward: f64,
+    pub strikes: Vec<f64>,
+    pub ivs: Vec<f64>,
+}
+
+impl SmileSlice {
+    fn log_moneyness(&self) -> Vec<f64> {
+        self.strikes.iter().map(|k| (k / self.forward).ln()).collect()
+    }
+
+    // ATM total variance by linear interpolation in log-moneyness.
+    fn atm_total_variance(&self) -> f64 {
+        let mut points: Vec<(f64, f64)> = self
+            .log_moneyness()
+            .into_iter()
+            .zip(self.ivs.iter().map(|iv| iv * iv * self.t))
+            .collect();
+        points.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        match points.iter().position(|(k, _)| *k >= 0.0) {
+            Some(0) => points[0].1,
+            Some(i) => {
+                let (k0, w0) = points[i - 1];
+                let (k1, w1) = points[i];
+                w0 + (w1 - w0) * (0.0 - k0) / (k1 - k0)
+            }
+            None => points[points.len() - 1].1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RawSvi {
+    pub a: f64,
+    pub b: f64,
+    pub rho: f64,
+    pub m: f64,
+    pub sigma: f64,
+}
+
+impl RawSvi {
+    pub fn total_variance(&self, k: f64) -> f64 {
+        let x = k - self.m;
+        self.a + self.b * (self.rho * x + (x * x + self.sigma * self.sigma).sqrt())
+    }
+
+    pub fn first_derivative(&self, k: f64) -> f64 {
+        let x = k - self.m;
+        self.b * (self.rho + x / (x * x + self.sigma * self.sigma).sqrt())
+    }
+
+    pub fn second_derivative(&self, k: f64) -> f64 {
+        let x = k - self.m;
+        let root = (x * x + self.sigma * self.sigma).sqrt();
+        self.b * self.sigma * self.sigma / (root * root * root)
+    }
+
+    pub fn durrleman_g(&self, k: f64) -> f64 {
+        crate::models::vol_surface::durrleman_g(
+            k,
+            self.total_variance(k),
+            self.first_derivative(k),
+            self.second_derivative(k),
+        )
+    }
+
+    // Minimum of w over k; must be non-negative for the slice to make sense.
+    pub fn min_total_variance(&self) -> f64 {
+        self.a + self.b * self.sigma * (1.0 - self.rho * self.rho).sqrt()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SviFit {
+    pub params: RawSvi,
+    pub rmse_iv: f64,
+    pub iterations: usize,
+}
+
+pub fn fit_raw_svi(slice: &SmileSlice) -> Result<SviFit, Box<dyn std::error::Error>> {
+    if slice.strikes.len() != slice.ivs.len() {
+        return Err("Strikes and IVs must have the same length".into());
+    }
+    if slice.strikes.len() < 5 {
+        return Err("Need at least 5 strikes to fit a raw SVI slice".into());
+    }
+
+    let ks = slice.log_moneyness();
+    let t = slice.t;
+    let max_w = slice.ivs.iter().map(|iv| iv * iv * t).fold(0.0, f64::max);
+    let k_span = ks.iter().fold(0.0_f64, |acc, k| acc.max(k.abs())).max(0.1);
+
+    let residuals = |p: &DVector<f64>| {
+        let svi = RawSvi { a: p[0], b: p[1], rho: p[2], m: p[3], sigma: p[4] };
+        DVector::from_iterator(
+            ks.len(),
+            ks.iter().zip(slice.ivs.iter()).map(|(&k, &iv)| {
+                (svi.total_variance(k).max(0.0) / t).sqrt() - iv
+            }),
+        )
+    };
+
+    let initial = DVector::from_vec(vec![0.5 * slice.atm_total_variance(), 0.1, -0.3, 0.0, 0.1]);
+    let fit = levenberg_marquardt(
+        residuals,
+        initial,
+        &[-max_w, 1e-6, -0.999, -k_span, 1e-4],
+        &[max_w, 5.0, 0.999, k_span, 2.0],
+        300,
+    );
+    let p = &fit.params;
+    let params = RawSvi { a: p[0], b: p[1], rho: p[2], m: p[3], sigma: p[4] };
+    if params.min_total_variance() < 0.0 {
+        return Err("Fitted SVI slice has negative total variance".into());
+    }
+
+    Ok(SviFit {
+        params,
+        rmse_iv: (fit.residuals.norm_squared() / ks.len() as f64).sqrt(),
+        iterations: fit.iterations,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SviSurface {
+    pub spot: f64,
+    pub q: f64,
+    pub rates: Vec<(f64, f64)>,
+    pub slices: Vec<(f64, RawSvi)>,
+}
+
+impl SviSurface {
+    pub fn fit(spot: f64, q: f64, slices: &[SmileSlice]) -> Result<Self, Box<dyn std::error::Error>> {
+        let slices: Vec<SmileSlice> = slices.iter().filter(|s| !s.strikes.is_empty()).cloned().collect();
+        let mut fitted = Vec::with_capacity(slices.len());
+        for slice in &slices {
+            fitted.push((slice.t, fit_raw_svi(slice)?.params));
+        }
+        fitted.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        if fitted.is_empty() {
+            return Err("Need at least one slice to build an SVI surface".into());
+        }
+        Ok(SviSurface { spot, q, rates: slice_rates(&slices), slices: fitted })
+    }
+}
+
+// Zero rate at each slice's expiry, sorted by expiry.
+fn slice_rates(slices: &[SmileSlice]) -> Vec<(f64, f64)> {
+    let mut rates: Vec<(f64, f64)> = slices.iter().map(|s| (s.t, s.r)).collect();
+    rates.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    rates
+}
+
+// Linear interpolation of r * t between slice expiries (flat forward rates),
+// with the nearest slice's zero rate outside them.
+fn interpolate_rate(rates: &[(f64, f64)], t: f64) -> f64 {
+    let last = rates.len() - 1;
+    if t <= rates[0].0 {
+        return rates[0].1;
+    }
+    if t >= rates[last].0 {
+        return rates[last].1;
+    }
+    let i = rates.iter().position(|&(ti, _)| ti >= t).unwrap();
+    let (t0, r0) = rates[i - 1];
+    let (t1, r1) = rates[i];
+    let weight = (t - t0) / (t1 - t0);
+    ((1.0 - weight) * r0 * t0 + weight * r1 * t1) / t
+}
+
+// Linear interpolation in total variance between slices at fixed
+// log-moneyness; flat implied vol outside the fitted expiries.
+fn interpolate_slices<F: Fn(usize) -> f64>(times: &[f64], t: f64, w_at: F) -> f64 {
+    let last = times.len() - 1;
+    if t <= times[0] {
+        return w_at(0) * t / times[0];
+    }
+    if t >= times[last] {
+        return w_at(last) * t / times[last];
+    }
+    let i = times.iter().position(|&ti| ti >= t).unwrap();
+    let weight = (t - times[i - 1]) / (times[i] - times[i - 1]);
+    (1.0 - weight) * w_at(i - 1) + weight * w_at(i)
+}
+
+impl VolatilitySurface for SviSurface {
+    fn forward(&self, t: f64) -> f64 {
+        self.spot * ((interpolate_rate(&self.rates, t) - self.q) * t).exp()
+    }
+
+    fn total_variance(&self, log_moneyness: f64, t: f64) -> f64 {
+        let times: Vec<f64> = self.slices.iter().map(|(ti, _)| *ti).collect();
+        interpolate_slices(&times, t, |i| self.slices[i].1.total_variance(log_moneyness))
+    }
+}
+
+// Gatheral-Jacquier SSVI with the power-law mixing function
+// phi(theta) = eta / (theta^gamma (1 + theta)^(1 - gamma)).
+#[derive(Debug, Clone, PartialEq)]
+pub struct SsviSurface {
+    pub spot: f64,
+    pub q: f64,
+    pub rates: Vec<(f64, f64)>,
+    pub rho: f64,
+    pub eta: f64,
+    pub gamma: f64,
+    pub atm_variances: Vec<(f64, f64)>,
+    pub rmse_iv: f64,
+}
+
+fn ssvi_phi(theta: f64, eta: f64, gamma: f64) -> f64 {
+    eta / (theta.powf(gamma) * (1.0 + theta).powf(1.0 - gamma))
+}
+
+fn ssvi_total_variance(k: f64, theta: f64, rho: f64, eta: f64, gamma: f64) -> f64 {
+    let phi = ssvi_phi(theta, eta, gamma);
+    let x = phi * k + rho;
+    0.5 * theta * (1.0 + rho * phi * k + (x * x + 1.0 - rho * rho).sqrt())
+}
+
+impl SsviSurface {
+    pub fn fit(spot: f64, q: f64, slices: &[SmileSlice]) -> Result<Self, Box<dyn std::error::Error>> {
+        // expiries without quotes carry no smile information
+        let slices: Vec<SmileSlice> = slices.iter().filter(|s| !s.strikes.is_empty()).cloned().collect();
+        if slices.iter().any(|s| s.strikes.len() != s.ivs.len()) {
+            return Err("Strikes and IVs must have the same length".into());
+        }
+        let rates = slice_rates(&slices);
+        let mut slices: Vec<&SmileSlice> = slices.iter().collect();
+        slices.sort_by(|a, b| a.t.partial_cmp(&b.t).unwrap());
+        let points: usize = slices.iter().map(|s| s.strikes.len()).sum();
+        if points < 3 {
+            return Err("Need at least 3 quotes to fit an SSVI surface".into());
+        }
+
+        let atm_variances: Vec<(f64, f64)> = slices.iter().map(|s| (s.t, s.atm_total_variance().max(1e-8))).collect();
+        let log_moneyness: Vec<Vec<f64>> = slices.iter().map(|s| s.log_moneyness()).collect();
+
+        let residuals = |p: &DVector<f64>| {
+            let mut out = Vec::with_capacity(points);
+            for (i, slice) in slices.iter().enumerate() {
+                let theta = atm_variances[i].1;
+                for (k, iv) in log_moneyness[i].iter().zip(slice.ivs.iter()) {
+                    let w = ssvi_total_variance(*k, theta, p[0], p[1], p[2]);
+                    out.push((w.max(0.0) / slice.t).sqrt() - iv);
+                }
+            }
+            DVector::from_vec(out)
+        };
+
+        let fit = levenberg_marquardt(
+            residuals,
+            DVector::from_vec(vec![-0.3, 0.5, 0.3]),
+            &[-0.999, 1e-4, 1e-3],
+            &[0.999, 10.0, 0.999],
+            300,
+        );
+
+        Ok(SsviSurface {
+            spot,
+            q,
+            rates,
+            rho: fit.params[0],
+            eta: fit.params[1],
+            gamma: fit.params[2],
+            atm_variances,
+            rmse_iv: (fit.residuals.norm_squared() / points as f64).sqrt(),
+        })
+    }
+
+    fn theta(&self, t: f64) -> f64 {
+        let times: Vec<f64> = self.atm_variances.iter().map(|(ti, _)| *ti).collect();
+        interpolate_slices(&times, t, |i| self.atm_variances[i].1)
+    }
+
+    // Gatheral-Jacquier sufficient conditions: theta * phi * (1 + |rho|) < 4 and
+    // theta * phi^2 * (1 + |rho|) <= 4 on every slice rule out butterfly
+    // arbitrage; a non-decreasing ATM variance term structure rules out
+    // calendar arbitrage for the power-law phi with gamma <= 1.
+    pub fn satisfies_no_arbitrage_conditions(&self) -> bool {
+        let butterfly = self.atm_variances.iter().all(|&(_, theta)| {
+            let phi = ssvi_phi(theta, self.eta, self.gamma);
+            theta * phi * (1.0 + self.rho.abs()) < 4.0 && theta * phi * phi * (1.0 + self.rho.abs()) <= 4.0
+        });
+        let calendar = self.atm_variances.windows(2).all(|pair| pair[1].1 >= pair[0].1);
+        butterfly && calendar
+    }
+}
+
+impl VolatilitySurface for SsviSurface {
+    fn forward(&self, t: f64) -> f64 {
+        self.spot * ((interpolate_rate(&self.rates, t) - self.q) * t).exp()
+    }
+
+    fn total_variance(&self, log_moneyness: f64, t: f64) -> f64 {
+        let theta = self.theta(t);
+        if theta <= 0.0 {
+            return 0.0;
+        }
+        ssvi_total_variance(log_moneyness, theta, self.rho, self.eta, self.gamma)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::vol_surface::check_arbitrage;
+
+    const SPOT: f64 = 100.0;
+    const Q: f64 = 0.01;
+
+    fn synthetic_slice(t: f64, r: f64, svi: &RawSvi) -> SmileSlice {
+        let forward = SPOT * ((r - Q) * t).exp();
+        let strikes: Vec<f64> = (-8..=8).map(|i| forward * (i as f64 * 0.05).exp()).collect();
+        let ivs = strikes.iter().map(|k| (svi.total_variance((k / forward).ln()) / t).sqrt()).collect();
+        SmileSlice { t, r, forward, strikes, ivs }
+    }
+
+    fn slices() -> Vec<SmileSlice> {
+        [(0.25, 0.05, 0.01), (0.5, 0.045, 0.02), (1.0, 0.04, 0.04)]
+            .iter()
+            .map(|&(t, r, a)| synthetic_slice(t, r, &RawSvi { a, b: 0.1, rho: -0.4, m: 0.02, sigma: 0.15 }))
+            .collect()
+    }
+
+    #[test]
+    fn surfaces_use_each_expiry_forward() {
+        let slices = slices();
+        let svi = SviSurface::fit(SPOT, Q, &slices).unwrap();
+        let ssvi = SsviSurface::fit(SPOT, Q, &slices).unwrap();
+        for slice in &slices {
+            assert!((svi.forward(slice.t) - slice.forward).abs() < 1e-10);
+            assert!((ssvi.forward(slice.t) - slice.forward).abs() < 1e-10);
+        }
+        // flat forward rate between expiries
+        let t = 0.75;
+        let expected = ((0.5 * 0.045 * 0.5 + 0.5 * 0.04 * 1.0) / t - Q) * t;
+        assert!((svi.forward(t) - SPOT * expected.exp()).abs() < 1e-10);
+    }
+
+    #[test]
+    fn raw_fit_recovers_a_known_slice() {
+        let truth = RawSvi { a: 0.02, b: 0.12, rho: -0.35, m: 0.03, sigma: 0.2 };
+        let slice = synthetic_slice(0.5, 0.04, &truth);
+        let fit = fit_raw_svi(&slice).unwrap();
+        assert!(fit.rmse_iv < 1e-5, "rmse {}", fit.rmse_iv);
+        for k in [-0.3, 0.0, 0.3] {
+            assert!((fit.params.total_variance(k) - truth.total_variance(k)).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn derivatives_match_finite_differences() {
+        let svi = RawSvi { a: 0.02, b: 0.12, rho: -0.35, m: 0.03, sigma: 0.2 };
+        let h = 1e-4;
+        for k in [-0.4, 0.0, 0.25] {
+            let dw = (svi.total_variance(k + h) - svi.total_variance(k - h)) / (2.0 * h);
+            let d2w = (svi.total_variance(k + h) - 2.0 * svi.total_variance(k) + svi.total_variance(k - h)) / (h * h);
+            assert!((svi.first_derivative(k) - dw).abs() < 1e-8);
+            assert!((svi.second_derivative(k) - d2w).abs() < 1e-5);
+        }
+        let min = (-2000..=2000).map(|i| svi.total_variance(i as f64 * 0.001)).fold(f64::INFINITY, f64::min);
+        assert!((svi.min_total_variance() - min).abs() < 1e-6);
+    }
+
+    #[test]
+    fn well_behaved_surfaces_pass_the_arbitrage_checks() {
+        let slices = slices();
+        let times: Vec<f64> = slices.iter().map(|s| s.t).collect();
+        let grid: Vec<f64> = (-20..=20).map(|i| i as f64 * 0.02).collect();
+        let svi = SviSurface::fit(SPOT, Q, &slices).unwrap();
+        assert!(check_arbitrage(&svi, &times, &grid).is_arbitrage_free());
+        let ssvi = SsviSurface::fit(SPOT, Q, &slices).unwrap();
+        assert!(ssvi.satisfies_no_arbitrage_conditions());
+        assert!(check_arbitrage(&ssvi, &times, &grid).is_arbitrage_free());
+    }
+
+    #[test]
+    fn crossing_slices_are_flagged() {
+        // a steeply skewed slice with negative density in the left wing, and a
+        // later expiry below it in total variance
+        let butterfly = RawSvi { a: -0.01, b: 0.9, rho: -0.95, m: 0.0, sigma: 0.05 };
+        assert!((-30..=30).any(|i| butterfly.durrleman_g(i as f64 * 0.02) < 0.0));
+        let surface = SviSurface {
+            spot: SPOT,
+            q: Q,
+            rates: vec![(0.5, 0.04), (1.0, 0.04)],
+            slices: vec![
+                (0.5, RawSvi { a: 0.03, b: 0.1, rho: -0.3, m: 0.0, sigma: 0.2 }),
+                (1.0, RawSvi { a: 0.01, b: 0.1, rho: -0.3, m: 0.0, sigma: 0.2 }),
+            ],
+        };
+        let report = check_arbitrage(&surface, &[0.5, 1.0], &[-0.2, 0.0, 0.2]);
+        assert_eq!(report.calendar_violations.len(), 3);
+    }
+
+    #[test]
+    fn empty_slices_are_skipped() {
+        let mut slices = slices();
+        slices.insert(1, SmileSlice { t: 0.4, r: 0.05, forward: SPOT, strikes: Vec::new(), ivs: Vec::new() });
+        let ssvi = SsviSurface::fit(SPOT, Q, &slices).unwrap();
+        assert_eq!(ssvi.atm_variances.len(), 3);
+        let svi = SviSurface::fit(SPOT, Q, &slices).unwrap();
+        assert_eq!(svi.slices.len(), 3);
+        let empty = [SmileSlice { t: 0.4, r: 0.05, forward: SPOT, strikes: Vec::new(), ivs: Vec::new() }];
+        assert!(SsviSurface::fit(SPOT, Q, &empty).is_err());
+        assert!(SviSurface::fit(SPOT, Q, &empty).is_err());
+    }
+}
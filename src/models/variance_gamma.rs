@@ -0,0 +1,142 @@
+use nalgebra::{Complex, DVector};
+use crate::models::fourier::{calibrate_fourier_model, CharacteristicFunction, ModelCalibration};
+use crate::models::calibration::CalibrationQuote;
+use crate::models::monte_carlo::{price_from_paths, sample_gamma, simulate_paths_with, standard_normal};
+use crate::models::payoff::Payoff;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VarianceGammaParams {
+    pub sigma: f64,
+    pub nu: f64,
+    pub theta: f64,
+}
+
+impl VarianceGammaParams {
+    pub fn new(sigma: f64, nu: f64, theta: f64) -> Self {
+        VarianceGammaParams { sigma, nu, theta }
+    }
+
+    // Drift correction making e^{X_t} a martingale.
+    pub fn omega(&self) -> f64 {
+        (1.0 - self.theta * self.nu - 0.5 * self.sigma * self.sigma * self.nu).ln() / self.nu
+    }
+}
+
+impl CharacteristicFunction for VarianceGammaParams {
+    fn characteristic_function(&self, u: Complex<f64>, t: f64) -> Complex<f64> {
+        let i = Complex::new(0.0, 1.0);
+        let base = 1.0 - i * u * self.theta * self.nu + 0.5 * self.sigma * self.sigma * self.nu * u * u;
+        (i * u * self.omega() * t).exp() * base.powf(-t / self.nu)
+    }
+}
+
+// Brownian motion with drift theta and vol sigma, run on a gamma clock with
+// unit mean rate and variance rate nu.
+pub fn simulate_variance_gamma_paths(
+    s0: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    params: &VarianceGammaParams,
+    num_steps: usize,
+    num_paths: usize,
+) -> Vec<Vec<f64>> {
+    let dt = t / num_steps as f64;
+    let drift = (r - q + params.omega()) * dt;
+
+    simulate_paths_with(s0, s0, num_steps, num_paths, |rng, current_price, _| {
+        let g = sample_gamma(rng, dt / params.nu, params.nu);
+        let x = params.theta * g + params.sigma * g.sqrt() * standard_normal(rng);
+        *current_price *= (drift + x).exp();
+        *current_price
+    })
+}
+
+pub fn variance_gamma_monte_carlo_price<P: Payoff>(
+    s0: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    params: &VarianceGammaParams,
+    payoff: &P,
+    num_steps: usize,
+    num_paths: usize,
+) -> f64 {
+    let paths = simulate_variance_gamma_paths(s0, t, r, q, params, num_steps, num_paths);
+    price_from_paths(&paths, t, r, payoff)
+}
+
+pub fn calibrate_variance_gamma(
+    s: f64,
+    q: f64,
+    quotes: &[CalibrationQuote],
+    initial: &VarianceGammaParams,
+) -> Result<ModelCalibration<VarianceGammaParams>, Box<dyn std::error::Error>> {
+    let build = |p: &DVector<f64>| VarianceGammaParams::new(p[0], p[1], p[2]);
+    let fit = calibrate_fourier_model(
+        s,
+        q,
+        quotes,
+        build,
+        DVector::from_vec(vec![initial.sigma, initial.nu, initial.theta]),
+        &[0.01, 0.01, -1.0],
+        &[2.0, 5.0, 1.0],
+        100,
+    )?;
+    let params = build(&fit.params);
+    if !params.omega().is_finite() {
+        return Err("Calibrated Variance Gamma parameters admit no martingale correction".into());
+    }
+    Ok(ModelCalibration { params, rmse_iv: fit.rmse_iv, iterations: fit.iterations, converged: fit.converged })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::fourier::{FourierMethod, fourier_price_strikes, fourier_smile};
+    use crate::models::payoff::{OptionType, Vanilla};
+
+    const S: f64 = 100.0;
+    const T: f64 = 1.0;
+    const R: f64 = 0.04;
+    const Q: f64 = 0.01;
+    const STRIKES: [f64; 7] = [80.0, 90.0, 95.0, 100.0, 105.0, 110.0, 120.0];
+
+    fn batched<F: FnMut() -> f64>(batches: usize, mut price: F) -> (f64, f64) {
+        let samples: Vec<f64> = (0..batches).map(|_| price()).collect();
+        let mean = samples.iter().sum::<f64>() / batches as f64;
+        let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (batches - 1) as f64;
+        (mean, (variance / batches as f64).sqrt())
+    }
+
+    #[test]
+    fn characteristic_function_matches_simulation() {
+        let params = VarianceGammaParams::new(0.2, 0.3, -0.15);
+        // one step is exact: the subordinator is sampled over the whole period
+        for option_type in [OptionType::Call, OptionType::Put] {
+            let prices = fourier_price_strikes(&params, S, &[90.0, 110.0], T, R, Q, option_type, FourierMethod::Cos);
+            for (&k, &cos) in [90.0, 110.0].iter().zip(prices.iter()) {
+                let payoff = Vanilla::new(option_type, k);
+                let (mc, se) = batched(20, || variance_gamma_monte_carlo_price(S, T, R, Q, &params, &payoff, 1, 5000));
+                assert!((mc - cos).abs() < 4.0 * se, "{option_type:?} {k}: {mc} +/- {se} vs {cos}");
+            }
+        }
+    }
+
+    #[test]
+    fn calibration_recovers_the_parameters() {
+        let truth = VarianceGammaParams::new(0.2, 0.3, -0.15);
+        let mut quotes = Vec::new();
+        for &t in &[0.25, 1.0, 2.0] {
+            for (strike, implied_vol) in fourier_smile(&truth, S, &STRIKES, t, R, Q, FourierMethod::Cos) {
+                quotes.push(CalibrationQuote { strike, t, r: R, implied_vol });
+            }
+        }
+        let fit = calibrate_variance_gamma(S, Q, &quotes, &VarianceGammaParams::new(0.3, 0.5, 0.0)).unwrap();
+        let p = fit.params;
+        assert!(fit.rmse_iv < 1e-5, "rmse {}", fit.rmse_iv);
+        assert!((p.sigma - truth.sigma).abs() < 1e-3, "{p:?}");
+        assert!((p.nu - truth.nu).abs() < 1e-2, "{p:?}");
+        assert!((p.theta - truth.theta).abs() < 1e-2, "{p:?}");
+    }
+}
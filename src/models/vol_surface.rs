@@ -0,0 +1,69 @@
+pub trait VolatilitySurface {
+    fn forward(&self, t: f64) -> f64;
+
+    // Total implied variance w(k, t) = sigma^2 t at log-moneyness k = ln(K / F(t)).
+    fn total_variance(&self, log_moneyness: f64, t: f64) -> f64;
+
+    fn implied_vol(&self, strike: f64, t: f64) -> f64 {
+        if t <= 0.0 {
+            return 0.0;
+        }
+        let k = (strike / self.forward(t)).ln();
+        (self.total_variance(k, t).max(0.0) / t).sqrt()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArbitrageReport {
+    pub butterfly_violations: Vec<(f64, f64)>,
+    pub calendar_violations: Vec<(f64, f64)>,
+}
+
+impl ArbitrageReport {
+    pub fn is_arbitrage_free(&self) -> bool {
+        self.butterfly_violations.is_empty() && self.calendar_violations.is_empty()
+    }
+}
+
+// Durrleman's condition: the risk-neutral density implied by a slice is
+// non-negative iff g(k) >= 0 everywhere.
+pub fn durrleman_g(k: f64, w: f64, dw: f64, d2w: f64) -> f64 {
+    let term = 1.0 - k * dw / (2.0 * w);
+    term * term - 0.25 * dw * dw * (1.0 / w + 0.25) + 0.5 * d2w
+}
+
+// Checks butterfly arbitrage on each expiry and calendar arbitrage between
+// consecutive expiries, using finite differences of the total variance on a
+// log-moneyness grid. Violations are reported as (t, k) pairs.
+pub fn check_arbitrage<S: VolatilitySurface>(
+    surface: &S,
+    expiries: &[f64],
+    log_moneyness: &[f64],
+) -> ArbitrageReport {
+    let h = 1e-4;
+    let mut butterfly_violations = Vec::new();
+    let mut calendar_violations = Vec::new();
+
+    for &t in expiries {
+        for &k in log_moneyness {
+            let w = surface.total_variance(k, t);
+            let w_up = surface.total_variance(k + h, t);
+            let w_down = surface.total_variance(k - h, t);
+            let dw = (w_up - w_down) / (2.0 * h);
+            let d2w = (w_up - 2.0 * w + w_down) / (h * h);
+            if w <= 0.0 || durrleman_g(k, w, dw, d2w) < -1e-8 {
+                butterfly_violations.push((t, k));
+            }
+        }
+    }
+
+    for pair in expiries.windows(2) {
+        for &k in log_moneyness {
+            if surface.total_variance(k, pair[1]) < surface.total_variance(k, pair[0]) - 1e-10 {
+                calendar_violations.push((pair[1], k));
+            }
+        }
+    }
+
+    ArbitrageReport { butterfly_violations, calendar_violations }
+}
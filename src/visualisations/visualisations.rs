@@ -1,50 +1,58 @@
 use plotters::prelude::*;
-use statrs::distribution::{Normal, ContinuousCDF, Continuous};
-use crate::models::black_scholes::{black_scholes_call, black_scholes_put};
+use statrs::distribution::{Normal, ContinuousCDF};
+use crate::models::calendar::TimeBasis;
+use crate::models::dividends::{CashDividend, escrowed_spot, present_value_of_dividends};
+use crate::models::greeks::black_scholes_greeks_with_dividends;
+use crate::models::payoff::{ExerciseStyle, Payoff};
+use crate::models::pde::crank_nicolson_price;
 use rand::prelude::*;
 use std::ops::Range;
 
-pub fn plot_pnl_distribution(
+pub fn plot_pnl_distribution<P: Payoff>(
     s0: f64,
-    k: f64,
     t: f64,
     r: f64,
+    q: f64,
     sigma: f64,
-    option_type: &str,
+    payoff: &P,
+    dividends: &[CashDividend],
     num_simulations: usize,
+    basis: TimeBasis,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let root = BitMapBackend::new("pnl_distribution.png", (800, 600)).into_drawing_area();
     root.fill(&WHITE)?;
 
-    let dt = t / 252.0;
-    let drift = r - 0.5 * sigma * sigma;
+    // One step per day of the chosen basis.
+    let num_steps = ((t * basis.days_per_year()).round() as usize).max(1);
+    let dt = t / num_steps as f64;
+    let drift = r - q - 0.5 * sigma * sigma;
     let vol = sigma * dt.sqrt();
 
     let normal = Normal::new(0.0, 1.0).unwrap();
     let mut rng = rand::rng();
 
-    let initial_price = match option_type {
-        "call" => black_scholes_call(s0, k, t, r, sigma),
-        _ => black_scholes_put(s0, k, t, r, sigma),
-    };
-
-    let mut pnls = Vec::with_capacity(num_simulations);
+    let mut payoffs = Vec::with_capacity(num_simulations);
 
+    // Escrowed-dividend model: every dividend has been paid by expiry, so the
+    // terminal spot is the escrowed spot's GBM.
+    let escrowed = escrowed_spot(s0, t, r, dividends);
     for _ in 0..num_simulations {
-        let mut price = s0;
-        for _ in 0..252 {
+        let mut price = escrowed;
+        for _ in 0..num_steps {
             let u: f64 = rng.random::<f64>();
             let z = normal.inverse_cdf(u);
             price *= (drift * dt + vol * z).exp();
         }
-        let payoff = match option_type {
-            "call" => (price - k).max(0.0),
-            _ => (k - price).max(0.0),
-        };
-        let pnl = payoff - initial_price;
-        pnls.push(pnl);
+        payoffs.push(payoff.payoff(price));
     }
 
+    // Closed-form premium for vanillas, the simulated fair value otherwise.
+    let initial_price = match payoff.vanilla() {
+        Some(vanilla) => black_scholes_greeks_with_dividends(s0, vanilla.strike, t, r, q, sigma, vanilla.option_type, dividends, basis).price,
+        None => (-r * t).exp() * payoffs.iter().sum::<f64>() / num_simulations as f64,
+    };
+    let pnls: Vec<f64> = payoffs.iter().map(|p| p - initial_price).collect();
+
     let min_pnl = pnls.iter().cloned().fold(f64::INFINITY, f64::min);
     let max_pnl = pnls.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
 
@@ -61,9 +69,7 @@ pub fn plot_pnl_distribution(
     let avg_pnl = pnls.iter().sum::<f64>() / pnls.len() as f64;
 
     let mut chart = ChartBuilder::on(&root)
-        .caption(format!("{} Option PnL Distribution (K=${:.2})",
-                        if option_type == "call" { "Call" } else { "Put" }, k),
-                ("sans-serif", 30))
+        .caption(format!("{} PnL Distribution", payoff.label()), ("sans-serif", 30))
         .margin(40)
         .x_label_area_size(40)
         .y_label_area_size(60)
@@ -116,17 +122,24 @@ pub fn plot_pnl_distribution(
     Ok(())
 }
 
-pub fn plot_time_decay(
+pub fn plot_time_decay<P: Payoff>(
     s0: f64,
-    k: f64,
     r: f64,
+    q: f64,
     sigma: f64,
     max_days: usize,
-    option_type: &str,
+    payoff: &P,
+    dividends: &[CashDividend],
+    basis: TimeBasis,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    
-    let max_days = max_days.min(60);
-    
+    // Payoffs without a closed form come from one PDE solve, which holds the
+    // value at every day to expiry.
+    let pde = match payoff.vanilla() {
+        None if max_days > 0 => Some(crank_nicolson_price(s0, max_days as f64 / basis.days_per_year(), r, q, sigma, payoff,
+                                                          &ExerciseStyle::European, None, 400, max_days, basis)),
+        _ => None,
+    };
+
     let root = BitMapBackend::new("time_decay_curve.png", (800, 600)).into_drawing_area();
     root.fill(&WHITE)?;
 
@@ -136,16 +149,21 @@ pub fn plot_time_decay(
     
     for day in 0..=max_days {
         let actual_day = max_days - day;
-        let t = actual_day as f64 / 365.0;
-        
-        let price = match option_type {
-            "call" => black_scholes_call(s0, k, t, r, sigma),
-            _ => black_scholes_put(s0, k, t, r, sigma),
-        };
-        
-        let intrinsic = match option_type {
-            "call" => (s0 - k).max(0.0),
-            _ => (k - s0).max(0.0),
+        let t = actual_day as f64 / basis.days_per_year();
+        // ex-dates are measured from today, so move them with the clock
+        let elapsed = day as f64 / basis.days_per_year();
+        let remaining: Vec<CashDividend> = dividends
+            .iter()
+            .map(|d| CashDividend::new(d.time - elapsed, d.amount))
+            .collect();
+        let intrinsic = payoff.payoff(s0);
+        let price = match (payoff.vanilla(), &pde) {
+            _ if t <= 0.0 => intrinsic,
+            (Some(vanilla), _) => {
+                black_scholes_greeks_with_dividends(s0, vanilla.strike, t, r, q, sigma, vanilla.option_type, &remaining, basis).price
+            }
+            (None, Some(pde)) => pde.grid.value_at(pde.grid.layer_at(t), escrowed_spot(s0, t, r, &remaining)).0,
+            (None, None) => intrinsic,
         };
         
         let time_value = price - intrinsic;
@@ -163,16 +181,17 @@ pub fn plot_time_decay(
     let y_max = max_price + price_range * 0.05;
 
     let mut chart = ChartBuilder::on(&root)
-        .caption(format!("{} Option Time Decay (K=${:.2})", 
-                        if option_type == "call" { "Call" } else { "Put" }, k), 
-                ("sans-serif", 30))
+        .caption(format!("{} Time Decay", payoff.label()), ("sans-serif", 30))
         .margin(40)
         .x_label_area_size(40)
         .y_label_area_size(60)
         .build_cartesian_2d(0f64..(max_days as f64), y_min..y_max)?;
 
     chart.configure_mesh()
-        .x_desc("Days Remaining Until Expiry")
+        .x_desc(match basis {
+            TimeBasis::Calendar => "Days Remaining Until Expiry",
+            TimeBasis::Trading => "Trading Days Remaining Until Expiry",
+        })
         .y_desc("Option Price ($)")
         .draw()?;
 
@@ -231,14 +250,16 @@ pub fn plot_volatility_smile(
     ivs: Vec<f64>,
     current_stock: f64,
     k: f64,
+    fitted_smiles: &[(&str, Vec<(f64, f64)>)],
 ) -> Result<(), Box<dyn std::error::Error>> {
     let root = BitMapBackend::new("volatility_smile.png", (800, 600)).into_drawing_area();
     root.fill(&WHITE)?;
 
     let min_strike = *strikes.iter().min_by(|a, b| a.partial_cmp(b).unwrap()).unwrap();
     let max_strike = *strikes.iter().max_by(|a, b| a.partial_cmp(b).unwrap()).unwrap();
-    let min_iv = *ivs.iter().min_by(|a, b| a.partial_cmp(b).unwrap()).unwrap();
-    let max_iv = *ivs.iter().max_by(|a, b| a.partial_cmp(b).unwrap()).unwrap();
+    let fitted_ivs = fitted_smiles.iter().flat_map(|(_, curve)| curve.iter().map(|(_, iv)| *iv));
+    let min_iv = ivs.iter().cloned().chain(fitted_ivs.clone()).fold(f64::INFINITY, f64::min);
+    let max_iv = ivs.iter().cloned().chain(fitted_ivs).fold(f64::NEG_INFINITY, f64::max);
 
     let mut chart = ChartBuilder::on(&root)
         .caption("Volatility Smile", ("sans-serif", 30))
@@ -266,6 +287,13 @@ pub fn plot_volatility_smile(
         &BLUE,
     ))?;
 
+    for (i, (label, curve)) in fitted_smiles.iter().enumerate() {
+        let color = Palette99::pick(i + 3).stroke_width(2);
+        chart.draw_series(LineSeries::new(curve.clone(), color))?
+            .label(*label)
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
+    }
+
     chart.draw_series(LineSeries::new(
         vec![(current_stock, min_iv * 0.95), (current_stock, max_iv * 1.05)],
         RED.mix(0.5).stroke_width(2),
@@ -289,18 +317,21 @@ pub fn plot_volatility_smile(
     Ok(())
 }
 
-pub fn plot_greeks(
+pub fn plot_greeks<P: Payoff>(
     s0: f64,
-    k: f64,
     t: f64,
     r: f64,
+    q: f64,
     sigma: f64,
-    option_type: &str,
+    payoff: &P,
+    dividends: &[CashDividend],
+    basis: TimeBasis,
 ) -> Result<(), Box<dyn std::error::Error>> {
     use plotters::prelude::*;
     let root = BitMapBackend::new("option_greeks.png", (800, 600)).into_drawing_area();
     root.fill(&WHITE)?;
 
+    let k = payoff.strike();
     let price_min = (s0.min(k)) * 0.8;
     let price_max = (s0.max(k)) * 1.2;
     let steps = 100;
@@ -310,12 +341,36 @@ pub fn plot_greeks(
     let mut theta_points = Vec::with_capacity(steps);
     let mut vega_points = Vec::with_capacity(steps);
 
-    for i in 0..steps {
-        let price = price_min + (price_max - price_min) * i as f64 / (steps as f64 - 1.0);
-        delta_points.push((price, calculate_delta(price, k, t, r, sigma, option_type)));
-        gamma_points.push((price, calculate_gamma(price, k, t, r, sigma)));
-        theta_points.push((price, calculate_theta(price, k, t, r, sigma, option_type)));
-        vega_points.push((price, calculate_vega(price, k, t, r, sigma)));
+    if let Some(vanilla) = payoff.vanilla() {
+        for i in 0..steps {
+            let price = price_min + (price_max - price_min) * i as f64 / (steps as f64 - 1.0);
+            let greeks = black_scholes_greeks_with_dividends(price, k, t, r, q, sigma, vanilla.option_type, dividends, basis);
+            delta_points.push((price, greeks.delta));
+            gamma_points.push((price, greeks.gamma));
+            theta_points.push((price, greeks.theta));
+            vega_points.push((price, greeks.vega));
+        }
+    } else {
+        // Other payoffs are read off the PDE grid at the escrowed spot, with
+        // the same units as the analytic suite: vega bumps the vol by one
+        // point and theta is per day of the chosen basis.
+        let base = crank_nicolson_price(s0, t, r, q, sigma, payoff, &ExerciseStyle::European, None, 400, 200, basis);
+        let bumped = crank_nicolson_price(s0, t, r, q, sigma + 0.01, payoff, &ExerciseStyle::European, None, 400, 200, basis);
+        let last = base.grid.values.len() - 1;
+        let layer_dt = base.grid.times_to_expiry[last] - base.grid.times_to_expiry[last - 1];
+        let accrual = r * present_value_of_dividends(t, r, dividends);
+
+        for i in 0..steps {
+            let price = price_min + (price_max - price_min) * i as f64 / (steps as f64 - 1.0);
+            let spot = escrowed_spot(price, t, r, dividends);
+            let (value, delta, gamma) = base.grid.value_at(last, spot);
+            let (previous, _, _) = base.grid.value_at(last - 1, spot);
+            let (bumped_value, _, _) = bumped.grid.value_at(last, spot);
+            delta_points.push((price, delta));
+            gamma_points.push((price, gamma));
+            theta_points.push((price, (-(value - previous) / layer_dt - delta * accrual) / basis.days_per_year()));
+            vega_points.push((price, bumped_value - value));
+        }
     }
 
     let mut chart = ChartBuilder::on(&root)
@@ -332,16 +387,16 @@ pub fn plot_greeks(
 
     chart.draw_series(LineSeries::new(delta_points, &BLUE))?
         .label("Delta")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &BLUE));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLUE));
     chart.draw_series(LineSeries::new(gamma_points, &GREEN))?
         .label("Gamma")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &GREEN));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], GREEN));
     chart.draw_series(LineSeries::new(theta_points, &RED))?
         .label("Theta")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &RED));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], RED));
     chart.draw_series(LineSeries::new(vega_points, &MAGENTA))?
         .label("Vega")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &MAGENTA));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], MAGENTA));
 
     chart.configure_series_labels()
         .background_style(WHITE.mix(0.8))
@@ -355,7 +410,8 @@ pub fn plot_greeks(
 pub fn plot_stock_paths(s0: f64,
     k: f64,
     t: f64,
-    paths: Vec<Vec<f64>>
+    paths: Vec<Vec<f64>>,
+    barrier: Option<f64>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let root = BitMapBackend::new("stock_price_paths.png", (800, 600)).into_drawing_area();
     root.fill(&WHITE)?;
@@ -372,6 +428,11 @@ pub fn plot_stock_paths(s0: f64,
         }
     }
 
+    if let Some(h) = barrier {
+        min_price = min_price.min(h);
+        max_price = max_price.max(h);
+    }
+
     let margin = (max_price - min_price) * 0.1;
     min_price -= margin;
     max_price += margin;
@@ -395,18 +456,34 @@ pub fn plot_stock_paths(s0: f64,
     .label("Strike Price")
     .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], RED.mix(0.5).stroke_width(2)));
 
+    if let Some(h) = barrier {
+        chart.draw_series(LineSeries::new(
+            vec![(0.0, h), (t, h)],
+            BLACK.stroke_width(2),
+        ))?
+        .label("Barrier")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLACK.stroke_width(2)));
+    }
+
     for (i, path) in paths.iter().enumerate() {
-        let color = Palette99::pick(i).mix(0.5);
+        let knocked = barrier.is_some_and(|h| {
+            if h < s0 {
+                path.iter().any(|&price| price <= h)
+            } else {
+                path.iter().any(|&price| price >= h)
+            }
+        });
+        let color = if knocked { RED.mix(0.9) } else { Palette99::pick(i).mix(0.3) };
         let points: Vec<(f64, f64)> = path.iter().enumerate()
             .map(|(step, &price)| (step as f64 * t / num_steps as f64, price))
             .collect();
             
-        chart.draw_series(LineSeries::new(points, color.stroke_width(1)))?;
+        chart.draw_series(LineSeries::new(points, color.stroke_width(if knocked { 2 } else { 1 })))?;
     }
     
     chart.configure_series_labels()
-        .background_style(&WHITE.mix(0.8))
-        .border_style(&BLACK)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
         .draw()?;
         
     println!("Stock price paths saved to stock_price_paths.png");
@@ -488,41 +565,3 @@ pub fn plot_iv_curve_reciprocal(
 
     Ok(())
 }
-
-fn d1(s: f64, k: f64, t: f64, r: f64, sigma: f64) -> f64 {
-    ((s / k).ln() + (r + 0.5 * sigma * sigma) * t) / (sigma * t.sqrt())
-}
-
-fn d2(s: f64, k: f64, t: f64, r: f64, sigma: f64) -> f64 {
-    d1(s, k, t, r, sigma) - sigma * t.sqrt()
-}
-
-pub fn calculate_delta(s: f64, k: f64, t: f64, r: f64, sigma: f64, option_type: &str) -> f64 {
-    let normal = Normal::new(0.0, 1.0).unwrap();
-    match option_type {
-        "call" => normal.cdf(d1(s, k, t, r, sigma)),
-        _ => normal.cdf(d1(s, k, t, r, sigma)) - 1.0,
-    }
-}
-
-pub fn calculate_gamma(s: f64, k: f64, t: f64, r: f64, sigma: f64) -> f64 {
-    let normal = Normal::new(0.0, 1.0).unwrap();
-    normal.pdf(d1(s, k, t, r, sigma)) / (s * sigma * t.sqrt())
-}
-
-pub fn calculate_theta(s: f64, k: f64, t: f64, r: f64, sigma: f64, option_type: &str) -> f64 {
-    let normal = Normal::new(0.0, 1.0).unwrap();
-    let d1 = d1(s, k, t, r, sigma);
-    let d2 = d2(s, k, t, r, sigma);
-    let first = - (s * normal.pdf(d1) * sigma) / (2.0 * t.sqrt());
-    let second = match option_type {
-        "call" => -r * k * (-r * t).exp() * normal.cdf(d2),
-        _ => r * k * (-r * t).exp() * normal.cdf(-d2),
-    };
-    (first + second) / 365.0
-}
-
-pub fn calculate_vega(s: f64, k: f64, t: f64, r: f64, sigma: f64) -> f64 {
-    let normal = Normal::new(0.0, 1.0).unwrap();
-    s * normal.pdf(d1(s, k, t, r, sigma)) * t.sqrt() / 100.0
-}
\ No newline at end of file